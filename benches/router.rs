@@ -0,0 +1,127 @@
+use bytes::Bytes;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use futures_util::FutureExt;
+use http::Uri;
+use http_body_util::{BodyExt, Empty};
+use hyper::client::conn::http1::{self, SendRequest};
+use hyper_util::rt::TokioIo;
+use oxidized::{http_request::Body, Path, Request, Response, Result, Router, Server, Service};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+use tokio::runtime::Runtime;
+
+const ROUTES: [(&str, &str); 3] = [
+    ("static_route", "/static/50"),
+    ("param_route", "/users/42/posts/7"),
+    ("not_found", "/missing"),
+];
+
+async fn index() -> Result<Response> {
+    Ok(Response::new("ok"))
+}
+
+async fn post(Path((user, post)): Path<(u32, u32)>) -> Result<Response> {
+    Ok(Response::new(format!("{}/{}", user, post)))
+}
+
+fn router() -> Router {
+    let mut router = Router::new().get("/users/:user/posts/:post", post);
+    for i in 0..100 {
+        router = router.get(&format!("/static/{}", i), index);
+    }
+    router
+}
+
+fn request(path: &'static str) -> Request {
+    let mut req = http::Request::new(Body::empty());
+    *req.uri_mut() = Uri::from_static(path);
+    Request::new(req)
+}
+
+fn call(c: &mut Criterion) {
+    let router = router();
+
+    for (name, path) in ROUTES {
+        let mut group = c.benchmark_group(format!("call/{}", name));
+        group.bench_function("dispatch", |b| {
+            b.iter_batched(
+                || request(path),
+                |req| router.call(req),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_function("complete", |b| {
+            b.iter_batched(
+                || request(path),
+                |req| router.call(req).now_or_never(),
+                BatchSize::SmallInput,
+            )
+        });
+        group.finish();
+    }
+}
+
+// The router used to clone its path-keyed HashMaps on every request before
+// dispatching. This measures that clone alone for a table of the same size.
+fn table_clone(c: &mut Criterion) {
+    let handler: Arc<dyn Fn() + Send + Sync> = Arc::new(|| {});
+    let mut table = HashMap::new();
+    table.insert("/users/:user/posts/:post".to_string(), handler.clone());
+    for i in 0..100 {
+        table.insert(format!("/static/{}", i), handler.clone());
+    }
+
+    c.bench_function("table_clone", |b| b.iter(|| table.clone()));
+}
+
+async fn connect(addr: SocketAddr) -> SendRequest<Empty<Bytes>> {
+    tokio::spawn(Server::new(router(), addr).run());
+
+    let stream = loop {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => break stream,
+            Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+        }
+    };
+    let (sender, conn) = http1::handshake(TokioIo::new(stream)).await.unwrap();
+    tokio::spawn(conn);
+    sender
+}
+
+async fn send(sender: &mut SendRequest<Empty<Bytes>>, path: &str) {
+    let req = hyper::Request::get(path)
+        .header("host", "localhost")
+        .body(Empty::new())
+        .unwrap();
+    sender.ready().await.unwrap();
+    let res = sender.send_request(req).await.unwrap();
+    res.into_body().collect().await.unwrap();
+}
+
+fn server(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .unwrap();
+    let mut sender = rt.block_on(connect(addr));
+
+    for (name, path) in ROUTES {
+        c.bench_function(&format!("server/{}", name), |b| {
+            b.iter_custom(|iters| {
+                rt.block_on(async {
+                    let start = Instant::now();
+                    for _ in 0..iters {
+                        send(&mut sender, path).await;
+                    }
+                    start.elapsed()
+                })
+            })
+        });
+    }
+}
+
+criterion_group!(benches, call, table_clone, server);
+criterion_main!(benches);
@@ -1,3 +1,5 @@
+use crate::{extractor::rejection::MultipartError, Response};
+use hyper::StatusCode;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -13,6 +15,31 @@ pub enum Error {
 
     #[error("Not found")]
     NotFound,
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    Internal(String),
+
+    #[error("{0}")]
+    Multipart(#[from] MultipartError),
 }
 
-pub type Result<T> = std::result::Result<T, Error>; 
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl From<Error> for Response {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::NotFound => Response::new("Not Found").status(StatusCode::NOT_FOUND),
+            Error::BadRequest(message) => Response::new(message).status(StatusCode::BAD_REQUEST),
+            Error::Internal(message) => {
+                Response::new(message).status(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+            Error::Multipart(rejection) => rejection.into(),
+            Error::Hyper(_) | Error::Http(_) | Error::Io(_) => {
+                Response::new("Internal Server Error").status(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    extractor::{
+        rejection::{BytesRejection, FailedToBufferBody, LengthLimitError},
+        FromRequest,
+    },
+    http_request::{Body, RequestParts},
+    Error,
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{ready, Stream};
+use http::header::CONTENT_LENGTH;
+use http_body_util::{BodyExt, Limited};
+use hyper::body::Body as _;
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pub(crate) const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+pub struct BodyStream {
+    pub(crate) body: Body,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BodyLimit(pub usize);
+
+#[async_trait]
+impl<S> FromRequest<S> for Bytes
+where
+    S: Send + Sync,
+{
+    type Rejection = BytesRejection;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        collect_body(parts, body).await
+    }
+}
+
+#[async_trait]
+impl<S> FromRequest<S> for BodyStream
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(
+        _parts: &mut RequestParts,
+        body: Body,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(BodyStream { body })
+    }
+}
+
+impl Stream for BodyStream {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(Pin::new(&mut self.body).poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    if let Ok(data) = frame.into_data() {
+                        return Poll::Ready(Some(Ok(data)));
+                    }
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hint = self.body.size_hint();
+        (0, hint.upper().map(|upper| upper as usize))
+    }
+}
+
+pub(crate) fn body_limit(parts: &RequestParts) -> usize {
+    parts
+        .extensions
+        .get::<BodyLimit>()
+        .map_or(DEFAULT_BODY_LIMIT, |limit| limit.0)
+}
+
+pub(crate) async fn collect_body(
+    parts: &RequestParts,
+    body: Body,
+) -> Result<Bytes, BytesRejection> {
+    let limit = body_limit(parts);
+    check_content_length(parts, limit)?;
+
+    match Limited::new(body, limit).collect().await {
+        Ok(body) => Ok(body.to_bytes()),
+        Err(err) if err.is::<http_body_util::LengthLimitError>() => {
+            Err(payload_too_large(limit).into())
+        }
+        Err(err) => Err(FailedToBufferBody(format!("Failed to read request body: {}", err)).into()),
+    }
+}
+
+pub(crate) fn check_content_length(
+    parts: &RequestParts,
+    limit: usize,
+) -> Result<(), LengthLimitError> {
+    let content_length = parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if content_length.is_some_and(|length| length > limit as u64) {
+        return Err(payload_too_large(limit));
+    }
+    Ok(())
+}
+
+fn payload_too_large(limit: usize) -> LengthLimitError {
+    LengthLimitError(format!("Request body exceeds the limit of {} bytes", limit))
+}
@@ -0,0 +1,42 @@
+use crate::{
+    extractor::{rejection::MissingConnectInfo, FromRequestParts},
+    http_request::RequestParts,
+};
+use async_trait::async_trait;
+use std::{any::type_name, net::SocketAddr};
+
+#[derive(Clone, Copy, Debug)]
+pub struct ConnectInfo<T>(pub T);
+
+#[derive(Clone, Debug)]
+pub struct ConnectionInfo {
+    pub peer_addr: SocketAddr,
+    pub local_addr: SocketAddr,
+    pub tls: bool,
+    pub alpn: Option<Vec<u8>>,
+}
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for ConnectInfo<T>
+where
+    S: Send + Sync,
+    T: Clone + Send + Sync + 'static,
+{
+    type Rejection = MissingConnectInfo;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<ConnectInfo<T>>()
+            .cloned()
+            .ok_or_else(|| {
+                MissingConnectInfo(format!(
+                    "Missing connection info `{}`, is the router served by `Server`?",
+                    type_name::<T>()
+                ))
+            })
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    extractor::FromRequestParts, http_request::RequestParts, response::IntoResponse, Response,
+};
+use async_trait::async_trait;
+use http::{
+    header::{COOKIE, SET_COOKIE},
+    HeaderMap, HeaderValue,
+};
+use std::convert::Infallible;
+
+mod private;
+mod signed;
+
+pub use self::private::PrivateCookieJar;
+pub use self::signed::SignedCookieJar;
+pub use ::cookie::{Cookie, Expiration, Key, SameSite};
+
+#[derive(Clone, Debug, Default)]
+pub struct CookieJar {
+    jar: ::cookie::CookieJar,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            jar: parse_cookies(headers),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Cookie<'static>> {
+        self.jar.get(name)
+    }
+
+    pub fn insert<C: Into<Cookie<'static>>>(mut self, cookie: C) -> Self {
+        self.jar.add(cookie);
+        self
+    }
+
+    pub fn remove<C: Into<Cookie<'static>>>(mut self, cookie: C) -> Self {
+        self.jar.remove(cookie);
+        self
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Cookie<'static>> {
+        self.jar.iter()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CookieJar
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers))
+    }
+}
+
+impl From<CookieJar> for Response {
+    fn from(jar: CookieJar) -> Self {
+        set_cookies(Response::new(""), &jar.jar)
+    }
+}
+
+impl<R> From<(CookieJar, R)> for Response
+where
+    R: IntoResponse,
+{
+    fn from((jar, res): (CookieJar, R)) -> Self {
+        set_cookies(res.into_response(), &jar.jar)
+    }
+}
+
+pub(crate) fn parse_cookies(headers: &HeaderMap) -> ::cookie::CookieJar {
+    let mut jar = ::cookie::CookieJar::new();
+    let cookies = headers
+        .get_all(COOKIE)
+        .into_iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|cookie| Cookie::parse_encoded(cookie.trim().to_owned()).ok());
+    for cookie in cookies {
+        jar.add_original(cookie);
+    }
+    jar
+}
+
+pub(crate) fn set_cookies(mut res: Response, jar: &::cookie::CookieJar) -> Response {
+    let headers = res.inner_mut().headers_mut();
+    for cookie in jar.delta() {
+        if let Ok(value) = HeaderValue::from_str(&cookie.encoded().to_string()) {
+            headers.append(SET_COOKIE, value);
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(cookies: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for cookie in cookies {
+            headers.append(COOKIE, HeaderValue::from_str(cookie).unwrap());
+        }
+        headers
+    }
+
+    fn set_cookie_headers(res: Response) -> Vec<String> {
+        let mut values: Vec<String> = res
+            .into_hyper()
+            .headers()
+            .get_all(SET_COOKIE)
+            .iter()
+            .map(|value| value.to_str().unwrap().to_string())
+            .collect();
+        values.sort();
+        values
+    }
+
+    fn echo(res: Response) -> HeaderMap {
+        let pairs: Vec<String> = set_cookie_headers(res)
+            .iter()
+            .map(|value| value.split(';').next().unwrap().to_string())
+            .collect();
+        headers(&[&pairs.join("; ")])
+    }
+
+    fn tamper(headers: &HeaderMap) -> HeaderMap {
+        let value = headers[COOKIE].to_str().unwrap();
+        let mut tampered = value.to_string();
+        let last = tampered.pop().unwrap();
+        tampered.push(if last == 'A' { 'B' } else { 'A' });
+        self::headers(&[&tampered])
+    }
+
+    #[test]
+    fn merges_several_cookie_headers() {
+        let jar = CookieJar::from_headers(&headers(&["a=1; b=2", "c=3"]));
+        assert_eq!(jar.get("a").unwrap().value(), "1");
+        assert_eq!(jar.get("b").unwrap().value(), "2");
+        assert_eq!(jar.get("c").unwrap().value(), "3");
+        assert_eq!(jar.iter().count(), 3);
+    }
+
+    #[test]
+    fn response_carries_only_the_delta() {
+        let jar = CookieJar::from_headers(&headers(&["kept=1; gone=2"]))
+            .insert(Cookie::new("added", "3"))
+            .remove(Cookie::from("gone"));
+        let values = set_cookie_headers((jar, "body").into());
+
+        assert_eq!(values.len(), 2, "{:?}", values);
+        assert_eq!(values[0], "added=3");
+        assert!(values[1].starts_with("gone=; "), "{}", values[1]);
+        assert!(values[1].contains("Max-Age=0"), "{}", values[1]);
+    }
+
+    #[test]
+    fn unchanged_jar_sets_no_cookies() {
+        let jar = CookieJar::from_headers(&headers(&["a=1"]));
+        assert!(set_cookie_headers(jar.into()).is_empty());
+    }
+
+    #[test]
+    fn signed_cookies_round_trip() {
+        let key = Key::generate();
+        let jar = SignedCookieJar::new(key.clone()).insert(Cookie::new("user", "42"));
+        let jar = SignedCookieJar::from_headers(&echo(jar.into()), key);
+        assert_eq!(jar.get("user").unwrap().value(), "42");
+    }
+
+    #[test]
+    fn signed_jar_rejects_tampered_and_unsigned_cookies() {
+        let key = Key::generate();
+        let jar = SignedCookieJar::new(key.clone()).insert(Cookie::new("user", "42"));
+        let signed = echo(jar.into());
+
+        assert!(SignedCookieJar::from_headers(&tamper(&signed), key.clone())
+            .get("user")
+            .is_none());
+        assert!(SignedCookieJar::from_headers(&signed, Key::generate())
+            .get("user")
+            .is_none());
+        assert!(SignedCookieJar::from_headers(&headers(&["user=42"]), key)
+            .get("user")
+            .is_none());
+    }
+
+    #[test]
+    fn signed_iter_skips_unverifiable_cookies() {
+        let key = Key::generate();
+        let jar = SignedCookieJar::new(key.clone()).insert(Cookie::new("user", "42"));
+        let signed = echo(jar.into());
+        let cookies = format!("{}; forged=1", signed[COOKIE].to_str().unwrap());
+        let jar = SignedCookieJar::from_headers(&headers(&[&cookies]), key);
+
+        let names: Vec<String> = jar.iter().map(|cookie| cookie.name().to_string()).collect();
+        assert_eq!(names, ["user"]);
+    }
+
+    #[test]
+    fn private_cookies_round_trip_encrypted() {
+        let key = Key::generate();
+        let jar = PrivateCookieJar::new(key.clone()).insert(Cookie::new("user", "42"));
+        let sent = echo(jar.into());
+        assert!(!sent[COOKIE].to_str().unwrap().contains("42"));
+
+        let jar = PrivateCookieJar::from_headers(&sent, key);
+        assert_eq!(jar.get("user").unwrap().value(), "42");
+    }
+
+    #[test]
+    fn private_jar_rejects_tampered_and_plain_cookies() {
+        let key = Key::generate();
+        let jar = PrivateCookieJar::new(key.clone()).insert(Cookie::new("user", "42"));
+        let sent = echo(jar.into());
+
+        assert!(PrivateCookieJar::from_headers(&tamper(&sent), key.clone())
+            .get("user")
+            .is_none());
+        assert!(PrivateCookieJar::from_headers(&sent, Key::generate())
+            .get("user")
+            .is_none());
+        let plain = PrivateCookieJar::from_headers(&headers(&["user=42; other=1"]), key);
+        assert!(plain.get("user").is_none());
+        assert_eq!(plain.iter().count(), 0);
+    }
+
+    #[test]
+    fn removing_a_signed_cookie_sends_a_removal_cookie() {
+        let key = Key::generate();
+        let jar = SignedCookieJar::new(key.clone()).insert(Cookie::new("user", "42"));
+        let jar =
+            SignedCookieJar::from_headers(&echo(jar.into()), key).remove(Cookie::from("user"));
+        let values = set_cookie_headers((jar, "bye").into());
+
+        assert_eq!(values.len(), 1, "{:?}", values);
+        assert!(values[0].starts_with("user=; "), "{}", values[0]);
+    }
+}
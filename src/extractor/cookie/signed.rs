@@ -0,0 +1,83 @@
+use crate::{
+    extractor::{
+        cookie::{parse_cookies, set_cookies, Cookie, Key},
+        FromRef, FromRequestParts,
+    },
+    http_request::RequestParts,
+    response::IntoResponse,
+    Response,
+};
+use async_trait::async_trait;
+use http::HeaderMap;
+use std::convert::Infallible;
+
+#[derive(Clone)]
+pub struct SignedCookieJar {
+    jar: ::cookie::CookieJar,
+    key: Key,
+}
+
+impl SignedCookieJar {
+    pub fn new(key: Key) -> Self {
+        Self {
+            jar: ::cookie::CookieJar::new(),
+            key,
+        }
+    }
+
+    pub fn from_headers(headers: &HeaderMap, key: Key) -> Self {
+        Self {
+            jar: parse_cookies(headers),
+            key,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Cookie<'static>> {
+        self.jar.signed(&self.key).get(name)
+    }
+
+    pub fn insert<C: Into<Cookie<'static>>>(mut self, cookie: C) -> Self {
+        self.jar.signed_mut(&self.key).add(cookie);
+        self
+    }
+
+    pub fn remove<C: Into<Cookie<'static>>>(mut self, cookie: C) -> Self {
+        self.jar.signed_mut(&self.key).remove(cookie);
+        self
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Cookie<'static>> + '_ {
+        self.jar.iter().filter_map(|cookie| self.get(cookie.name()))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for SignedCookieJar
+where
+    S: Send + Sync,
+    Key: FromRef<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_headers(&parts.headers, Key::from_ref(state)))
+    }
+}
+
+impl From<SignedCookieJar> for Response {
+    fn from(jar: SignedCookieJar) -> Self {
+        set_cookies(Response::new(""), &jar.jar)
+    }
+}
+
+impl<R> From<(SignedCookieJar, R)> for Response
+where
+    R: IntoResponse,
+{
+    fn from((jar, res): (SignedCookieJar, R)) -> Self {
+        set_cookies(res.into_response(), &jar.jar)
+    }
+}
@@ -0,0 +1,751 @@
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    Visitor,
+};
+use serde::forward_to_deserialize_any;
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug)]
+pub(crate) struct DeError(String);
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for DeError {}
+
+impl de::Error for DeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+pub(crate) struct PathDeserializer<'de> {
+    params: &'de [(String, String)],
+}
+
+impl<'de> PathDeserializer<'de> {
+    pub(crate) fn new(params: &'de [(String, String)]) -> Self {
+        Self { params }
+    }
+
+    fn single(&self) -> Result<ValueDeserializer<'de>, DeError> {
+        match self.params {
+            [(key, value)] => Ok(ValueDeserializer { key, value }),
+            params => Err(DeError(format!(
+                "expected 1 path parameter, found {}",
+                params.len()
+            ))),
+        }
+    }
+}
+
+macro_rules! forward_to_single {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.single()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for PathDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    forward_to_single! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_option
+        deserialize_identifier
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(ParamSeq {
+            params: self.params.iter(),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if self.params.len() != len {
+            return Err(DeError(format!(
+                "expected {} path parameters, found {}",
+                len,
+                self.params.len()
+            )));
+        }
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(ParamMap {
+            params: self.params.iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct ParamSeq<'de> {
+    params: std::slice::Iter<'de, (String, String)>,
+}
+
+impl<'de> SeqAccess<'de> for ParamSeq<'de> {
+    type Error = DeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.params.next() {
+            Some((key, value)) => seed.deserialize(ValueDeserializer { key, value }).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct ParamMap<'de> {
+    params: std::slice::Iter<'de, (String, String)>,
+    value: Option<(&'de str, &'de str)>,
+}
+
+impl<'de> MapAccess<'de> for ParamMap<'de> {
+    type Error = DeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.params.next() {
+            Some((key, value)) => {
+                self.value = Some((key, value));
+                seed.deserialize(key.as_str().into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .value
+            .take()
+            .ok_or_else(|| DeError("value requested before key".to_string()))?;
+        seed.deserialize(ValueDeserializer { key, value })
+    }
+}
+
+pub(crate) fn parse_urlencoded(input: &[u8]) -> Vec<(String, Vec<String>)> {
+    let mut entries: Vec<(String, Vec<String>)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (key, value) in form_urlencoded::parse(input) {
+        match index.get(key.as_ref()) {
+            Some(&i) => entries[i].1.push(value.into_owned()),
+            None => {
+                index.insert(key.to_string(), entries.len());
+                entries.push((key.into_owned(), vec![value.into_owned()]));
+            }
+        }
+    }
+    entries
+}
+
+pub(crate) struct QueryDeserializer<'de> {
+    entries: &'de [(String, Vec<String>)],
+}
+
+impl<'de> QueryDeserializer<'de> {
+    pub(crate) fn new(entries: &'de [(String, Vec<String>)]) -> Self {
+        Self { entries }
+    }
+}
+
+impl<'de> Deserializer<'de> for QueryDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(QueryMap {
+            entries: self.entries.iter(),
+            value: None,
+        })
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct QueryMap<'de> {
+    entries: std::slice::Iter<'de, (String, Vec<String>)>,
+    value: Option<(&'de str, &'de [String])>,
+}
+
+impl<'de> MapAccess<'de> for QueryMap<'de> {
+    type Error = DeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        match self.entries.next() {
+            Some((key, values)) => {
+                self.value = Some((key, values));
+                seed.deserialize(key.as_str().into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, values) = self
+            .value
+            .take()
+            .ok_or_else(|| DeError("value requested before key".to_string()))?;
+        seed.deserialize(MultiValueDeserializer { key, values })
+    }
+}
+
+struct MultiValueDeserializer<'de> {
+    key: &'de str,
+    values: &'de [String],
+}
+
+impl<'de> MultiValueDeserializer<'de> {
+    fn single(&self) -> Result<ValueDeserializer<'de>, DeError> {
+        match self.values.last() {
+            Some(value) => Ok(ValueDeserializer {
+                key: self.key,
+                value,
+            }),
+            None => Err(DeError(format!("missing value for `{}`", self.key))),
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for MultiValueDeserializer<'de> {
+    type Error = DeError;
+
+    forward_to_single! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32
+        deserialize_u64 deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char
+        deserialize_str deserialize_string deserialize_bytes deserialize_byte_buf
+        deserialize_unit deserialize_map deserialize_identifier
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.values.last() {
+            Some(value) if value.is_empty() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(ValueSeq {
+            key: self.key,
+            values: self.values.iter(),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single()?.deserialize_struct(name, fields, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.single()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+}
+
+struct ValueSeq<'de> {
+    key: &'de str,
+    values: std::slice::Iter<'de, String>,
+}
+
+impl<'de> SeqAccess<'de> for ValueSeq<'de> {
+    type Error = DeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        match self.values.next() {
+            Some(value) => seed
+                .deserialize(ValueDeserializer {
+                    key: self.key,
+                    value,
+                })
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+struct ValueDeserializer<'de> {
+    key: &'de str,
+    value: &'de str,
+}
+
+macro_rules! parse_value {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                let value = self.value.parse().map_err(|_| {
+                    DeError(format!(
+                        "cannot parse `{}` value `{}` as {}",
+                        self.key,
+                        self.value,
+                        stringify!($method).trim_start_matches("deserialize_"),
+                    ))
+                })?;
+                visitor.$visit(value)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.value)
+    }
+
+    parse_value! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.value.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(self)
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+impl<'de> EnumAccess<'de> for ValueDeserializer<'de> {
+    type Error = DeError;
+    type Variant = UnitOnly;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(self.value.into_deserializer())?;
+        Ok((variant, UnitOnly))
+    }
+}
+
+struct UnitOnly;
+
+impl<'de> de::VariantAccess<'de> for UnitOnly {
+    type Error = DeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        _seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        Err(unit_only())
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(unit_only())
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(unit_only())
+    }
+}
+
+fn unit_only() -> DeError {
+    DeError("only unit enum variants are supported".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{de::DeserializeOwned, Deserialize};
+    use std::time::{Duration, Instant};
+
+    fn query<T: DeserializeOwned>(input: &str) -> Result<T, DeError> {
+        let entries = parse_urlencoded(input.as_bytes());
+        T::deserialize(QueryDeserializer::new(&entries))
+    }
+
+    fn path<T: DeserializeOwned>(params: &[(&str, &str)]) -> Result<T, DeError> {
+        let params: Vec<(String, String)> = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        T::deserialize(PathDeserializer::new(&params))
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Search {
+        q: String,
+        page: u32,
+        exact: bool,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Tags {
+        tag: Vec<String>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Ids {
+        id: Vec<u32>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Page {
+        page: u32,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Filter {
+        limit: Option<u32>,
+        name: Option<String>,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Order {
+        Asc,
+        Desc,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Sort {
+        order: Order,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Shape {
+        Circle(u32),
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Draw {
+        shape: Shape,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Wide {
+        big: i128,
+        huge: u128,
+    }
+
+    #[test]
+    fn deserializes_struct_fields() {
+        let search: Search = query("q=hello+world%21&page=2&exact=true").unwrap();
+        assert_eq!(
+            search,
+            Search {
+                q: "hello world!".to_string(),
+                page: 2,
+                exact: true
+            }
+        );
+    }
+
+    #[test]
+    fn collects_repeated_keys_into_vec() {
+        let tags: Tags = query("tag=a&tag=b&tag=c").unwrap();
+        assert_eq!(tags.tag, vec!["a", "b", "c"]);
+
+        let tags: Tags = query("tag=a").unwrap();
+        assert_eq!(tags.tag, vec!["a"]);
+
+        let ids: Ids = query("id=1&id=2").unwrap();
+        assert_eq!(ids.id, vec![1, 2]);
+    }
+
+    #[test]
+    fn last_value_wins_for_scalars() {
+        let page: Page = query("page=1&page=3").unwrap();
+        assert_eq!(page, Page { page: 3 });
+    }
+
+    #[test]
+    fn options_are_none_when_missing_or_empty() {
+        let filter: Filter = query("").unwrap();
+        assert_eq!(
+            filter,
+            Filter {
+                limit: None,
+                name: None
+            }
+        );
+
+        let filter: Filter = query("limit=&name=").unwrap();
+        assert_eq!(
+            filter,
+            Filter {
+                limit: None,
+                name: None
+            }
+        );
+
+        let filter: Filter = query("limit=5&name=bob").unwrap();
+        assert_eq!(
+            filter,
+            Filter {
+                limit: Some(5),
+                name: Some("bob".to_string())
+            }
+        );
+    }
+
+    #[test]
+    fn deserializes_unit_enum_variants() {
+        let sort: Sort = query("order=desc").unwrap();
+        assert_eq!(sort.order, Order::Desc);
+
+        let err = query::<Sort>("order=sideways").unwrap_err();
+        assert!(
+            err.to_string().contains("unknown variant `sideways`"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn rejects_non_unit_enum_variants() {
+        let err = query::<Draw>("shape=Circle").unwrap_err();
+        assert_eq!(err.to_string(), "only unit enum variants are supported");
+    }
+
+    #[test]
+    fn parses_128_bit_integers() {
+        let wide: Wide = query(&format!("big={}&huge={}", i128::MIN, u128::MAX)).unwrap();
+        assert_eq!(
+            wide,
+            Wide {
+                big: i128::MIN,
+                huge: u128::MAX
+            }
+        );
+
+        let value: u128 = path(&[("id", "340282366920938463463374607431768211455")]).unwrap();
+        assert_eq!(value, u128::MAX);
+    }
+
+    #[test]
+    fn reports_unparsable_values() {
+        let err = query::<Page>("page=two").unwrap_err();
+        assert_eq!(err.to_string(), "cannot parse `page` value `two` as u32");
+
+        let err = query::<Page>("").unwrap_err();
+        assert_eq!(err.to_string(), "missing field `page`");
+    }
+
+    #[test]
+    fn parses_many_distinct_keys_in_linear_time() {
+        let input = (0..100_000)
+            .map(|i| format!("k{}=", i))
+            .collect::<Vec<_>>()
+            .join("&");
+        let start = Instant::now();
+        let entries = parse_urlencoded(format!("{}&k7=x", input).as_bytes());
+        assert!(
+            start.elapsed() < Duration::from_secs(5),
+            "{:?}",
+            start.elapsed()
+        );
+
+        assert_eq!(entries.len(), 100_000);
+        assert_eq!(entries[0], ("k0".to_string(), vec![String::new()]));
+        assert_eq!(entries[7].1, ["", "x"]);
+        assert_eq!(entries[99_999].0, "k99999");
+    }
+
+    #[test]
+    fn deserializes_maps() {
+        let map: HashMap<String, String> = query("a=1&b=2").unwrap();
+        assert_eq!(map.get("a").map(String::as_str), Some("1"));
+        assert_eq!(map.get("b").map(String::as_str), Some("2"));
+    }
+
+    #[test]
+    fn path_enum_and_option() {
+        let order: Order = path(&[("order", "asc")]).unwrap();
+        assert_eq!(order, Order::Asc);
+
+        let id: Option<u32> = path(&[("id", "9")]).unwrap();
+        assert_eq!(id, Some(9));
+    }
+}
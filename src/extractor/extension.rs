@@ -0,0 +1,35 @@
+use crate::{
+    extractor::{rejection::MissingExtension, FromRequestParts},
+    http_request::RequestParts,
+};
+use async_trait::async_trait;
+use std::any::type_name;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Extension<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for Extension<T>
+where
+    S: Send + Sync,
+    T: Clone + Send + Sync + 'static,
+{
+    type Rejection = MissingExtension;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<T>()
+            .cloned()
+            .map(Extension)
+            .ok_or_else(|| {
+                MissingExtension(format!(
+                    "Missing request extension `{}`, is it added with `AddExtensionLayer`?",
+                    type_name::<T>()
+                ))
+            })
+    }
+}
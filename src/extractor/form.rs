@@ -0,0 +1,74 @@
+use crate::{
+    extractor::{
+        body::collect_body,
+        de::{parse_urlencoded, QueryDeserializer},
+        has_content_type,
+        rejection::{FailedToDeserializeForm, FormRejection, InvalidFormContentType},
+        ser::to_urlencoded,
+        FromRequest,
+    },
+    http_request::{Body, RequestParts},
+    Response,
+};
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{header::CONTENT_TYPE, HeaderValue, Method, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+
+const FORM_CONTENT_TYPE: &str = "application/x-www-form-urlencoded";
+
+pub struct Form<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequest<S> for Form<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Send,
+{
+    type Rejection = FormRejection;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let input = if parts.method == Method::GET || parts.method == Method::HEAD {
+            Bytes::copy_from_slice(parts.uri.query().unwrap_or_default().as_bytes())
+        } else {
+            if !has_content_type(parts, FORM_CONTENT_TYPE) {
+                return Err(InvalidFormContentType(format!(
+                    "Expected request with `Content-Type: {}`",
+                    FORM_CONTENT_TYPE
+                ))
+                .into());
+            }
+            collect_body(parts, body).await?
+        };
+
+        let entries = parse_urlencoded(&input);
+        T::deserialize(QueryDeserializer::new(&entries))
+            .map(Form)
+            .map_err(|err| {
+                FailedToDeserializeForm(format!("Failed to deserialize form: {}", err)).into()
+            })
+    }
+}
+
+impl<T> From<Form<T>> for Response
+where
+    T: Serialize,
+{
+    fn from(Form(value): Form<T>) -> Self {
+        match to_urlencoded(&value) {
+            Ok(body) => {
+                let mut res = Response::new(body);
+                res.inner_mut()
+                    .headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_static(FORM_CONTENT_TYPE));
+                res
+            }
+            Err(err) => Response::new(format!("Failed to serialize form: {}", err))
+                .status(StatusCode::INTERNAL_SERVER_ERROR),
+        }
+    }
+}
@@ -1,22 +1,135 @@
 use crate::{
-    extractor::FromRequest,
+    extractor::{
+        body::collect_body,
+        content_type_mime,
+        rejection::{JsonDataError, JsonRejection, JsonSyntaxError, MissingJsonContentType},
+        FromRequest,
+    },
     http_request::{Body, RequestParts},
-    Error, Request, Result,
 };
 use async_trait::async_trait;
-use http_body_util::BodyExt;
 use serde::de::DeserializeOwned;
+use serde_json::error::Category;
 
 pub struct Json<T>(pub T);
 
 #[async_trait]
-impl<T> FromRequest for Json<T>
+impl<S, T> FromRequest<S> for Json<T>
 where
+    S: Send + Sync,
     T: DeserializeOwned + Send,
 {
-    async fn from_request(_parts: &mut RequestParts, body: Body) -> Result<Self> {
-        let body_bytes = body.collect().await.map_err(|_| Error::NotFound)?.to_bytes();
-        let data = serde_json::from_slice(&body_bytes).map_err(|_| Error::NotFound)?;
-        Ok(Json(data))
+    type Rejection = JsonRejection;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        if !has_json_content_type(parts) {
+            return Err(MissingJsonContentType(
+                "Expected request with `Content-Type: application/json`".to_string(),
+            )
+            .into());
+        }
+
+        let body_bytes = collect_body(parts, body).await?;
+        deserialize(&body_bytes).map(Json)
+    }
+}
+
+fn has_json_content_type(parts: &RequestParts) -> bool {
+    let Some(mime) = content_type_mime(parts).map(str::to_ascii_lowercase) else {
+        return false;
+    };
+
+    mime == "application/json"
+        || mime
+            .strip_prefix("application/")
+            .is_some_and(|subtype| subtype.ends_with("+json"))
+}
+
+fn deserialize<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, JsonRejection> {
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|err| {
+        let path = err.path().to_string();
+        rejection(
+            err.inner(),
+            Some(path).filter(|path| path != "." && path != "?"),
+        )
+    })?;
+
+    deserializer.end().map_err(|err| rejection(&err, None))?;
+    Ok(value)
+}
+
+fn rejection(err: &serde_json::Error, path: Option<String>) -> JsonRejection {
+    let message = match path {
+        Some(path) => format!(
+            "Failed to parse the request body as JSON: {}: {}",
+            path, err
+        ),
+        None => format!("Failed to parse the request body as JSON: {}", err),
+    };
+
+    match err.classify() {
+        Category::Data => JsonDataError(message).into(),
+        Category::Syntax | Category::Eof | Category::Io => JsonSyntaxError(message).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_request::PathParams;
+    use http::{header::CONTENT_TYPE, Extensions, HeaderMap, HeaderValue, Method, Uri, Version};
+
+    fn parts(content_type: Option<&'static str>) -> RequestParts {
+        let mut headers = HeaderMap::new();
+        if let Some(content_type) = content_type {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+        }
+        RequestParts {
+            method: Method::POST,
+            uri: Uri::from_static("/"),
+            version: Version::HTTP_11,
+            headers,
+            params: PathParams::default(),
+            extensions: Extensions::new(),
+        }
+    }
+
+    #[test]
+    fn accepts_json_and_json_suffixes() {
+        for content_type in [
+            "application/json",
+            "Application/JSON; charset=utf-8",
+            " application/json ;charset=utf-8",
+            "application/problem+json",
+            "application/vnd.api+JSON",
+        ] {
+            assert!(
+                has_json_content_type(&parts(Some(content_type))),
+                "{}",
+                content_type
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_other_content_types() {
+        for content_type in [
+            "text/json",
+            "application/jsonx",
+            "application/json+xml",
+            "text/plain",
+        ] {
+            assert!(
+                !has_json_content_type(&parts(Some(content_type))),
+                "{}",
+                content_type
+            );
+        }
+        assert!(!has_json_content_type(&parts(None)));
     }
-} 
\ No newline at end of file
+}
@@ -1,39 +1,162 @@
 use crate::{
     http_request::{Body, RequestParts},
-    Error, Result,
+    response::IntoResponse,
 };
 use async_trait::async_trait;
-use http_body_util::BodyExt;
-use serde::de::DeserializeOwned;
+use std::convert::Infallible;
+
+pub(crate) mod body;
+mod connect_info;
+pub mod cookie;
+mod de;
+mod extension;
+mod form;
+mod json;
+pub mod multipart;
+mod path;
+mod query;
+pub mod rejection;
+mod request_parts;
+mod ser;
+mod state;
+mod string;
+mod typed_header;
+pub mod validated;
+
+pub use self::body::BodyStream;
+pub use self::connect_info::{ConnectInfo, ConnectionInfo};
+pub use self::cookie::{CookieJar, PrivateCookieJar, SignedCookieJar};
+pub use self::extension::Extension;
+pub use self::form::Form;
+pub use self::json::Json;
+pub use self::multipart::Multipart;
+pub use self::path::Path;
+pub use self::query::Query;
+pub use self::request_parts::MatchedPath;
+pub use self::state::{FromRef, State};
+pub use self::typed_header::TypedHeader;
+pub use self::validated::{Validate, Validated, ValidationErrors};
+
+mod private {
+    pub enum ViaParts {}
+    pub enum ViaRequest {}
+}
 
 #[async_trait]
-pub trait FromRequest: Sized {
-    async fn from_request(parts: &mut RequestParts) -> Result<Self>;
+pub trait FromRequestParts<S>: Sized {
+    type Rejection: IntoResponse;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection>;
 }
 
 #[async_trait]
-pub trait FromBody: Sized {
-    async fn from_body(body: Body) -> Result<Self>;
+pub trait FromRequest<S, M = private::ViaRequest>: Sized {
+    type Rejection: IntoResponse;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        state: &S,
+    ) -> Result<Self, Self::Rejection>;
 }
 
-pub struct Json<T>(pub T);
+#[async_trait]
+impl<S, T> FromRequest<S, private::ViaParts> for T
+where
+    S: Send + Sync,
+    T: FromRequestParts<S>,
+{
+    type Rejection = T::Rejection;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        _body: Body,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        T::from_request_parts(parts, state).await
+    }
+}
 
 #[async_trait]
-impl<T> FromBody for Json<T>
+impl<S, T> FromRequestParts<S> for Option<T>
 where
-    T: DeserializeOwned + Send,
+    S: Send + Sync,
+    T: FromRequestParts<S>,
 {
-    async fn from_body(body: Body) -> Result<Self> {
-        let body_bytes = body.collect().await.map_err(|_| Error::NotFound)?.to_bytes();
-        let data = serde_json::from_slice(&body_bytes).map_err(|_| Error::NotFound)?;
-        Ok(Json(data))
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(T::from_request_parts(parts, state).await.ok())
     }
 }
 
 #[async_trait]
-impl FromBody for String {
-    async fn from_body(body: Body) -> Result<Self> {
-        let body_bytes = body.collect().await.map_err(|_| Error::NotFound)?.to_bytes();
-        String::from_utf8(body_bytes.to_vec()).map_err(|_| Error::NotFound)
+impl<S, T> FromRequest<S> for Option<T>
+where
+    S: Send + Sync,
+    T: FromRequest<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(T::from_request(parts, body, state).await.ok())
     }
-} 
\ No newline at end of file
+}
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for Result<T, T::Rejection>
+where
+    S: Send + Sync,
+    T: FromRequestParts<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(T::from_request_parts(parts, state).await)
+    }
+}
+
+#[async_trait]
+impl<S, T> FromRequest<S> for Result<T, T::Rejection>
+where
+    S: Send + Sync,
+    T: FromRequest<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(T::from_request(parts, body, state).await)
+    }
+}
+
+pub(crate) fn content_type_mime(parts: &RequestParts) -> Option<&str> {
+    parts
+        .headers
+        .get(http::header::CONTENT_TYPE)?
+        .to_str()
+        .ok()?
+        .split(';')
+        .next()
+        .map(str::trim)
+}
+
+pub(crate) fn has_content_type(parts: &RequestParts, expected: &str) -> bool {
+    content_type_mime(parts).is_some_and(|mime| mime.eq_ignore_ascii_case(expected))
+}
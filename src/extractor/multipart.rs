@@ -0,0 +1,172 @@
+use crate::{
+    extractor::{
+        body::{body_limit, check_content_length},
+        has_content_type,
+        rejection::{
+            InvalidBoundary, InvalidMultipartBody, InvalidMultipartContentType, InvalidUtf8,
+            LengthLimitError, MultipartError, MultipartRejection,
+        },
+        BodyStream, FromRequest,
+    },
+    http_request::{Body, RequestParts},
+};
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures_util::{Stream, StreamExt};
+use http::HeaderMap;
+use multer::{Constraints, SizeLimit};
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+const MULTIPART_CONTENT_TYPE: &str = "multipart/form-data";
+
+pub struct Multipart {
+    pending: Option<(BodyStream, String)>,
+    inner: Option<multer::Multipart<'static>>,
+    body_limit: u64,
+    field_limit: Option<u64>,
+    total_limit: Option<u64>,
+}
+
+pub struct Field<'a> {
+    inner: multer::Field<'static>,
+    _multipart: PhantomData<&'a mut Multipart>,
+}
+
+#[async_trait]
+impl<S> FromRequest<S> for Multipart
+where
+    S: Send + Sync,
+{
+    type Rejection = MultipartRejection;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        if !has_content_type(parts, MULTIPART_CONTENT_TYPE) {
+            return Err(InvalidMultipartContentType(format!(
+                "Expected request with `Content-Type: {}`",
+                MULTIPART_CONTENT_TYPE
+            ))
+            .into());
+        }
+
+        let limit = body_limit(parts);
+        check_content_length(parts, limit)?;
+
+        let boundary = parts
+            .headers
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| multer::parse_boundary(value).ok())
+            .ok_or_else(|| InvalidBoundary("Missing multipart boundary".to_string()))?;
+
+        Ok(Self {
+            pending: Some((BodyStream { body }, boundary)),
+            inner: None,
+            body_limit: limit as u64,
+            field_limit: None,
+            total_limit: None,
+        })
+    }
+}
+
+impl Multipart {
+    pub fn with_field_limit(mut self, bytes: u64) -> Self {
+        self.field_limit = Some(bytes);
+        self
+    }
+
+    pub fn with_total_limit(mut self, bytes: u64) -> Self {
+        self.total_limit = Some(bytes);
+        self
+    }
+
+    pub async fn next_field(&mut self) -> Result<Option<Field<'_>>, MultipartError> {
+        let field = self.reader().next_field().await.map_err(multipart_error)?;
+        Ok(field.map(|inner| Field {
+            inner,
+            _multipart: PhantomData,
+        }))
+    }
+
+    fn reader(&mut self) -> &mut multer::Multipart<'static> {
+        let total_limit = self
+            .total_limit
+            .map_or(self.body_limit, |limit| limit.min(self.body_limit));
+        let size_limit = SizeLimit::new()
+            .whole_stream(total_limit)
+            .per_field(self.field_limit.unwrap_or(u64::MAX));
+        let pending = &mut self.pending;
+        self.inner.get_or_insert_with(|| {
+            let (body, boundary) = pending.take().expect("multipart body is read only once");
+            let constraints = Constraints::new().size_limit(size_limit);
+            multer::Multipart::with_constraints(body, boundary, constraints)
+        })
+    }
+}
+
+impl Field<'_> {
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    pub fn file_name(&self) -> Option<&str> {
+        self.inner.file_name()
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.inner.content_type().map(|mime| mime.as_ref())
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        self.inner.headers()
+    }
+
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, MultipartError> {
+        self.next().await.transpose()
+    }
+
+    pub async fn bytes(mut self) -> Result<Bytes, MultipartError> {
+        let mut buf = BytesMut::new();
+        while let Some(chunk) = self.chunk().await? {
+            buf.extend_from_slice(&chunk);
+        }
+        Ok(buf.freeze())
+    }
+
+    pub async fn text(self) -> Result<String, MultipartError> {
+        let bytes = self.bytes().await?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| InvalidUtf8("Multipart field is not valid UTF-8".to_string()).into())
+    }
+}
+
+impl Stream for Field<'_> {
+    type Item = Result<Bytes, MultipartError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner)
+            .poll_next(cx)
+            .map(|chunk| chunk.map(|chunk| chunk.map_err(multipart_error)))
+    }
+}
+
+fn multipart_error(err: multer::Error) -> MultipartError {
+    match err {
+        multer::Error::FieldSizeExceeded { field_name, .. } => LengthLimitError(format!(
+            "Multipart field `{}` exceeds the size limit",
+            field_name.unwrap_or_default()
+        ))
+        .into(),
+        multer::Error::StreamSizeExceeded { .. } => {
+            LengthLimitError("Multipart body exceeds the size limit".to_string()).into()
+        }
+        err => InvalidMultipartBody(format!("Invalid multipart body: {}", err)).into(),
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    extractor::{de::PathDeserializer, rejection::InvalidPathParams, FromRequestParts},
+    http_request::RequestParts,
+};
+use async_trait::async_trait;
+use percent_encoding::percent_decode_str;
+use serde::de::DeserializeOwned;
+
+pub struct Path<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for Path<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Send,
+{
+    type Rejection = InvalidPathParams;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let params = parts
+            .params
+            .iter()
+            .map(|(key, value)| {
+                let value = percent_decode_str(value).decode_utf8().map_err(|_| {
+                    InvalidPathParams(format!("Invalid URL: `{}` is not valid UTF-8", key))
+                })?;
+                Ok((key.to_string(), value.into_owned()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        T::deserialize(PathDeserializer::new(&params))
+            .map(Path)
+            .map_err(|err| InvalidPathParams(format!("Invalid URL: {}", err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_request::PathParams;
+    use http::{Extensions, HeaderMap, Method, Uri, Version};
+    use serde::Deserialize;
+
+    async fn extract<T>(params: &[(&str, &str)]) -> Result<T, InvalidPathParams>
+    where
+        T: DeserializeOwned + Send,
+    {
+        let mut route = String::new();
+        let mut path = String::new();
+        for (key, value) in params {
+            route.push_str(&format!("/:{}", key));
+            path.push_str(&format!("/{}", value));
+        }
+        let uri: Uri = path.parse().unwrap();
+        let mut parts = RequestParts {
+            method: Method::GET,
+            uri: uri.clone(),
+            version: Version::HTTP_11,
+            headers: HeaderMap::new(),
+            params: PathParams::new(route.into(), uri),
+            extensions: Extensions::new(),
+        };
+        Path::<T>::from_request_parts(&mut parts, &())
+            .await
+            .map(|Path(value)| value)
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Post {
+        user: String,
+        id: u64,
+    }
+
+    #[tokio::test]
+    async fn extracts_scalar() {
+        assert_eq!(extract::<u32>(&[("id", "42")]).await.unwrap(), 42);
+        assert_eq!(
+            extract::<String>(&[("name", "alice")]).await.unwrap(),
+            "alice"
+        );
+    }
+
+    #[tokio::test]
+    async fn extracts_tuple() {
+        let value = extract::<(String, u64)>(&[("user", "alice"), ("id", "7")]).await;
+        assert_eq!(value.unwrap(), ("alice".to_string(), 7));
+    }
+
+    #[tokio::test]
+    async fn extracts_struct_by_name() {
+        let value = extract::<Post>(&[("id", "7"), ("user", "alice")]).await;
+        assert_eq!(
+            value.unwrap(),
+            Post {
+                user: "alice".to_string(),
+                id: 7
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn percent_decodes_values() {
+        let value = extract::<String>(&[("name", "hello%20w%C3%B6rld")]).await;
+        assert_eq!(value.unwrap(), "hello wörld");
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_utf8() {
+        let err = extract::<String>(&[("name", "%FF")]).await.unwrap_err();
+        assert_eq!(err.status(), http::StatusCode::BAD_REQUEST);
+        assert_eq!(err.body_text(), "Invalid URL: `name` is not valid UTF-8");
+    }
+
+    #[tokio::test]
+    async fn rejects_unparsable_scalar() {
+        let err = extract::<u32>(&[("id", "abc")]).await.unwrap_err();
+        assert_eq!(
+            err.body_text(),
+            "Invalid URL: cannot parse `id` value `abc` as u32"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_tuple_length_mismatch() {
+        let err = extract::<(u32, u32)>(&[("id", "1")]).await.unwrap_err();
+        assert_eq!(
+            err.body_text(),
+            "Invalid URL: expected 2 path parameters, found 1"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_scalar_with_several_params() {
+        let err = extract::<u32>(&[("a", "1"), ("b", "2")]).await.unwrap_err();
+        assert_eq!(
+            err.body_text(),
+            "Invalid URL: expected 1 path parameter, found 2"
+        );
+    }
+}
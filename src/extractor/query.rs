@@ -0,0 +1,35 @@
+use crate::{
+    extractor::{
+        de::{parse_urlencoded, QueryDeserializer},
+        rejection::FailedToDeserializeQuery,
+        FromRequestParts,
+    },
+    http_request::RequestParts,
+};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+pub struct Query<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for Query<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned + Send,
+{
+    type Rejection = FailedToDeserializeQuery;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let entries = parse_urlencoded(query.as_bytes());
+
+        T::deserialize(QueryDeserializer::new(&entries))
+            .map(Query)
+            .map_err(|err| {
+                FailedToDeserializeQuery(format!("Failed to deserialize query string: {}", err))
+            })
+    }
+}
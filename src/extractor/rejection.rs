@@ -0,0 +1,149 @@
+use crate::Response;
+use http::StatusCode;
+use std::fmt;
+
+macro_rules! define_rejection {
+    ($($name:ident => $status:ident,)*) => {
+        $(
+            #[derive(Debug)]
+            pub struct $name(pub(crate) String);
+
+            impl $name {
+                pub fn status(&self) -> StatusCode {
+                    StatusCode::$status
+                }
+
+                pub fn body_text(&self) -> &str {
+                    &self.0
+                }
+            }
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(&self.0)
+                }
+            }
+
+            impl std::error::Error for $name {}
+
+            impl From<$name> for Response {
+                fn from(rejection: $name) -> Self {
+                    Response::new(rejection.0).status(StatusCode::$status)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! composite_rejection {
+    ($(pub enum $name:ident { $($variant:ident,)* })*) => {
+        $(
+            #[derive(Debug)]
+            pub enum $name {
+                $($variant($variant),)*
+            }
+
+            impl $name {
+                pub fn status(&self) -> StatusCode {
+                    match self {
+                        $(Self::$variant(rejection) => rejection.status(),)*
+                    }
+                }
+
+                pub fn body_text(&self) -> &str {
+                    match self {
+                        $(Self::$variant(rejection) => rejection.body_text(),)*
+                    }
+                }
+            }
+
+            $(
+                impl From<$variant> for $name {
+                    fn from(rejection: $variant) -> Self {
+                        Self::$variant(rejection)
+                    }
+                }
+            )*
+
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str(self.body_text())
+                }
+            }
+
+            impl std::error::Error for $name {}
+
+            impl From<$name> for Response {
+                fn from(rejection: $name) -> Self {
+                    match rejection {
+                        $($name::$variant(rejection) => rejection.into(),)*
+                    }
+                }
+            }
+        )*
+    };
+}
+
+define_rejection! {
+    InvalidPathParams => BAD_REQUEST,
+    FailedToDeserializeQuery => BAD_REQUEST,
+    InvalidFormContentType => UNSUPPORTED_MEDIA_TYPE,
+    FailedToDeserializeForm => BAD_REQUEST,
+    MissingJsonContentType => UNSUPPORTED_MEDIA_TYPE,
+    JsonSyntaxError => BAD_REQUEST,
+    JsonDataError => UNPROCESSABLE_ENTITY,
+    LengthLimitError => PAYLOAD_TOO_LARGE,
+    FailedToBufferBody => BAD_REQUEST,
+    InvalidUtf8 => BAD_REQUEST,
+    InvalidMultipartContentType => UNSUPPORTED_MEDIA_TYPE,
+    InvalidBoundary => BAD_REQUEST,
+    InvalidMultipartBody => BAD_REQUEST,
+    MissingHeader => BAD_REQUEST,
+    InvalidHeader => BAD_REQUEST,
+    MissingExtension => INTERNAL_SERVER_ERROR,
+    MissingConnectInfo => INTERNAL_SERVER_ERROR,
+    MissingSession => INTERNAL_SERVER_ERROR,
+    MissingMatchedPath => INTERNAL_SERVER_ERROR,
+}
+
+composite_rejection! {
+    pub enum BytesRejection {
+        LengthLimitError,
+        FailedToBufferBody,
+    }
+
+    pub enum StringRejection {
+        BytesRejection,
+        InvalidUtf8,
+    }
+
+    pub enum JsonRejection {
+        MissingJsonContentType,
+        BytesRejection,
+        JsonSyntaxError,
+        JsonDataError,
+    }
+
+    pub enum FormRejection {
+        InvalidFormContentType,
+        BytesRejection,
+        FailedToDeserializeForm,
+    }
+
+    pub enum MultipartRejection {
+        InvalidMultipartContentType,
+        InvalidBoundary,
+        LengthLimitError,
+    }
+
+    pub enum MultipartError {
+        InvalidMultipartBody,
+        LengthLimitError,
+        InvalidUtf8,
+    }
+
+    pub enum TypedHeaderRejection {
+        MissingHeader,
+        InvalidHeader,
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    extractor::{rejection::MissingMatchedPath, FromRequestParts},
+    http_request::RequestParts,
+    router::OriginalUri,
+};
+use async_trait::async_trait;
+use http::{HeaderMap, Method, Uri, Version};
+use std::{convert::Infallible, sync::Arc};
+
+#[derive(Clone, Debug)]
+pub struct MatchedPath(pub(crate) Arc<str>);
+
+impl MatchedPath {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for MatchedPath
+where
+    S: Send + Sync,
+{
+    type Rejection = MissingMatchedPath;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        parts
+            .params
+            .route()
+            .cloned()
+            .map(MatchedPath)
+            .ok_or_else(|| MissingMatchedPath("No route was matched for this request".to_string()))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for OriginalUri
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<OriginalUri>()
+            .cloned()
+            .unwrap_or_else(|| OriginalUri(parts.uri.clone())))
+    }
+}
+
+macro_rules! impl_from_parts {
+    ($($ty:ty => $field:ident,)*) => {
+        $(
+            #[async_trait]
+            impl<S> FromRequestParts<S> for $ty
+            where
+                S: Send + Sync,
+            {
+                type Rejection = Infallible;
+
+                async fn from_request_parts(
+                    parts: &mut RequestParts,
+                    _state: &S,
+                ) -> Result<Self, Self::Rejection> {
+                    Ok(parts.$field.clone())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_parts! {
+    Method => method,
+    Uri => uri,
+    Version => version,
+    HeaderMap => headers,
+}
@@ -0,0 +1,598 @@
+use serde::ser::{
+    self, Impossible, Serialize, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple,
+    SerializeTupleStruct, Serializer,
+};
+use std::fmt;
+
+type Encoder = form_urlencoded::Serializer<'static, String>;
+
+#[derive(Debug)]
+pub(crate) struct SerError(String);
+
+impl fmt::Display for SerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for SerError {}
+
+impl ser::Error for SerError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+pub(crate) fn to_urlencoded<T: Serialize + ?Sized>(value: &T) -> Result<String, SerError> {
+    let mut encoder = form_urlencoded::Serializer::new(String::new());
+    value.serialize(FormSerializer {
+        encoder: &mut encoder,
+    })?;
+    Ok(encoder.finish())
+}
+
+macro_rules! unsupported {
+    ($($method:ident($($arg:ty),*) -> $ok:ty,)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<$ok, Self::Error> {
+                Err(self.unsupported())
+            }
+        )*
+    };
+}
+
+struct FormSerializer<'a> {
+    encoder: &'a mut Encoder,
+}
+
+impl FormSerializer<'_> {
+    fn unsupported(&self) -> SerError {
+        SerError("form values must serialize to a map or struct".to_string())
+    }
+}
+
+impl<'a> Serializer for FormSerializer<'a> {
+    type Ok = ();
+    type Error = SerError;
+    type SerializeSeq = Impossible<(), SerError>;
+    type SerializeTuple = Impossible<(), SerError>;
+    type SerializeTupleStruct = Impossible<(), SerError>;
+    type SerializeTupleVariant = Impossible<(), SerError>;
+    type SerializeMap = FormMap<'a>;
+    type SerializeStruct = FormMap<'a>;
+    type SerializeStructVariant = Impossible<(), SerError>;
+
+    unsupported! {
+        serialize_bool(bool) -> (),
+        serialize_i8(i8) -> (),
+        serialize_i16(i16) -> (),
+        serialize_i32(i32) -> (),
+        serialize_i64(i64) -> (),
+        serialize_i128(i128) -> (),
+        serialize_u8(u8) -> (),
+        serialize_u16(u16) -> (),
+        serialize_u32(u32) -> (),
+        serialize_u64(u64) -> (),
+        serialize_u128(u128) -> (),
+        serialize_f32(f32) -> (),
+        serialize_f64(f64) -> (),
+        serialize_char(char) -> (),
+        serialize_str(&str) -> (),
+        serialize_bytes(&[u8]) -> (),
+        serialize_unit_variant(&'static str, u32, &'static str) -> (),
+        serialize_seq(Option<usize>) -> Self::SerializeSeq,
+        serialize_tuple(usize) -> Self::SerializeTuple,
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct,
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant,
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant,
+    }
+
+    fn serialize_none(self) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<FormMap<'a>, SerError> {
+        Ok(FormMap {
+            encoder: self.encoder,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<FormMap<'a>, SerError> {
+        Ok(FormMap {
+            encoder: self.encoder,
+            key: None,
+        })
+    }
+}
+
+struct FormMap<'a> {
+    encoder: &'a mut Encoder,
+    key: Option<String>,
+}
+
+impl SerializeMap for FormMap<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerError> {
+        let key = key
+            .serialize(ScalarSerializer { key: "" })
+            .map_err(|_| SerError("form keys must be scalars".to_string()))?;
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| SerError("value serialized before key".to_string()))?;
+        value.serialize(ValueSerializer {
+            key: &key,
+            encoder: self.encoder,
+        })
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+impl SerializeStruct for FormMap<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerError> {
+        value.serialize(ValueSerializer {
+            key,
+            encoder: self.encoder,
+        })
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+struct ValueSerializer<'a> {
+    key: &'a str,
+    encoder: &'a mut Encoder,
+}
+
+impl ValueSerializer<'_> {
+    fn unsupported(&self) -> SerError {
+        not_scalar(self.key)
+    }
+
+    fn append(self, value: &str) -> Result<(), SerError> {
+        self.encoder.append_pair(self.key, value);
+        Ok(())
+    }
+}
+
+macro_rules! append_scalar {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<(), SerError> {
+                let value = ScalarSerializer { key: self.key }.$method(value)?;
+                self.append(&value)
+            }
+        )*
+    };
+}
+
+impl<'a> Serializer for ValueSerializer<'a> {
+    type Ok = ();
+    type Error = SerError;
+    type SerializeSeq = FormSeq<'a>;
+    type SerializeTuple = FormSeq<'a>;
+    type SerializeTupleStruct = FormSeq<'a>;
+    type SerializeTupleVariant = Impossible<(), SerError>;
+    type SerializeMap = Impossible<(), SerError>;
+    type SerializeStruct = Impossible<(), SerError>;
+    type SerializeStructVariant = Impossible<(), SerError>;
+
+    append_scalar! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+    }
+
+    unsupported! {
+        serialize_bytes(&[u8]) -> (),
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant,
+        serialize_map(Option<usize>) -> Self::SerializeMap,
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct,
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant,
+    }
+
+    fn serialize_str(self, value: &str) -> Result<(), SerError> {
+        self.append(value)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerError> {
+        self.append(variant)
+    }
+
+    fn serialize_none(self) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<FormSeq<'a>, SerError> {
+        Ok(FormSeq {
+            key: self.key,
+            encoder: self.encoder,
+        })
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<FormSeq<'a>, SerError> {
+        self.serialize_seq(None)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<FormSeq<'a>, SerError> {
+        self.serialize_seq(None)
+    }
+}
+
+struct FormSeq<'a> {
+    key: &'a str,
+    encoder: &'a mut Encoder,
+}
+
+impl FormSeq<'_> {
+    fn append<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        let value = value.serialize(ScalarSerializer { key: self.key })?;
+        self.encoder.append_pair(self.key, &value);
+        Ok(())
+    }
+}
+
+impl SerializeSeq for FormSeq<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.append(value)
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+impl SerializeTuple for FormSeq<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.append(value)
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+impl SerializeTupleStruct for FormSeq<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.append(value)
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+struct ScalarSerializer<'a> {
+    key: &'a str,
+}
+
+impl ScalarSerializer<'_> {
+    fn unsupported(&self) -> SerError {
+        not_scalar(self.key)
+    }
+}
+
+macro_rules! display_scalar {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<String, SerError> {
+                Ok(value.to_string())
+            }
+        )*
+    };
+}
+
+impl Serializer for ScalarSerializer<'_> {
+    type Ok = String;
+    type Error = SerError;
+    type SerializeSeq = Impossible<String, SerError>;
+    type SerializeTuple = Impossible<String, SerError>;
+    type SerializeTupleStruct = Impossible<String, SerError>;
+    type SerializeTupleVariant = Impossible<String, SerError>;
+    type SerializeMap = Impossible<String, SerError>;
+    type SerializeStruct = Impossible<String, SerError>;
+    type SerializeStructVariant = Impossible<String, SerError>;
+
+    display_scalar! {
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_f32(f32),
+        serialize_f64(f64),
+        serialize_char(char),
+        serialize_str(&str),
+    }
+
+    unsupported! {
+        serialize_bytes(&[u8]) -> String,
+        serialize_unit() -> String,
+        serialize_unit_struct(&'static str) -> String,
+        serialize_seq(Option<usize>) -> Self::SerializeSeq,
+        serialize_tuple(usize) -> Self::SerializeTuple,
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct,
+        serialize_tuple_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeTupleVariant,
+        serialize_map(Option<usize>) -> Self::SerializeMap,
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct,
+        serialize_struct_variant(&'static str, u32, &'static str, usize)
+            -> Self::SerializeStructVariant,
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, SerError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_none(self) -> Result<String, SerError> {
+        Ok(String::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, SerError> {
+        Err(self.unsupported())
+    }
+}
+
+fn not_scalar(key: &str) -> SerError {
+    SerError(format!("`{}` is not a scalar or a list of scalars", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    struct Login {
+        username: String,
+        remember: bool,
+        attempts: u32,
+    }
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Order {
+        Desc,
+    }
+
+    #[derive(Serialize)]
+    struct Search {
+        zebra: u8,
+        apple: Option<u8>,
+        missing: Option<u8>,
+        tag: Vec<&'static str>,
+        order: Order,
+    }
+
+    #[derive(Serialize)]
+    struct Precise {
+        big: u64,
+        wide: i128,
+        ratio: f64,
+    }
+
+    #[derive(Serialize)]
+    struct Nested {
+        inner: Login,
+    }
+
+    #[test]
+    fn keeps_field_order_and_encodes_values() {
+        let login = Login {
+            username: "jürgen & co".to_string(),
+            remember: true,
+            attempts: 3,
+        };
+        assert_eq!(
+            to_urlencoded(&login).unwrap(),
+            "username=j%C3%BCrgen+%26+co&remember=true&attempts=3"
+        );
+    }
+
+    #[test]
+    fn writes_lists_as_repeated_keys_and_skips_none() {
+        let search = Search {
+            zebra: 1,
+            apple: Some(2),
+            missing: None,
+            tag: vec!["a", "b"],
+            order: Order::Desc,
+        };
+        assert_eq!(
+            to_urlencoded(&search).unwrap(),
+            "zebra=1&apple=2&tag=a&tag=b&order=desc"
+        );
+    }
+
+    #[test]
+    fn keeps_full_numeric_precision() {
+        let precise = Precise {
+            big: u64::MAX,
+            wide: i128::MIN,
+            ratio: 0.1 + 0.2,
+        };
+        assert_eq!(
+            to_urlencoded(&precise).unwrap(),
+            format!(
+                "big={}&wide={}&ratio=0.30000000000000004",
+                u64::MAX,
+                i128::MIN
+            )
+        );
+    }
+
+    #[test]
+    fn serializes_maps() {
+        let map = BTreeMap::from([("a", 1), ("b", 2)]);
+        assert_eq!(to_urlencoded(&map).unwrap(), "a=1&b=2");
+    }
+
+    #[test]
+    fn rejects_values_that_are_not_forms() {
+        assert_eq!(
+            to_urlencoded(&5).unwrap_err().to_string(),
+            "form values must serialize to a map or struct"
+        );
+
+        let nested = Nested {
+            inner: Login {
+                username: String::new(),
+                remember: false,
+                attempts: 0,
+            },
+        };
+        assert_eq!(
+            to_urlencoded(&nested).unwrap_err().to_string(),
+            "`inner` is not a scalar or a list of scalars"
+        );
+
+        let lists = BTreeMap::from([("list", vec![vec![1]])]);
+        assert_eq!(
+            to_urlencoded(&lists).unwrap_err().to_string(),
+            "`list` is not a scalar or a list of scalars"
+        );
+    }
+}
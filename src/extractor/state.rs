@@ -0,0 +1,32 @@
+use crate::{extractor::FromRequestParts, http_request::RequestParts};
+use async_trait::async_trait;
+use std::convert::Infallible;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct State<T>(pub T);
+
+pub trait FromRef<T> {
+    fn from_ref(input: &T) -> Self;
+}
+
+impl<T: Clone> FromRef<T> for T {
+    fn from_ref(input: &T) -> Self {
+        input.clone()
+    }
+}
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for State<T>
+where
+    S: Send + Sync,
+    T: FromRef<S>,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        _parts: &mut RequestParts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        Ok(State(T::from_ref(state)))
+    }
+}
@@ -1,15 +1,27 @@
 use crate::{
-    extractor::FromRequest,
+    extractor::{
+        body::collect_body,
+        rejection::{InvalidUtf8, StringRejection},
+        FromRequest,
+    },
     http_request::{Body, RequestParts},
-    Error, Result,
 };
 use async_trait::async_trait;
-use http_body_util::BodyExt;
 
 #[async_trait]
-impl FromRequest for String {
-    async fn from_request(_parts: &mut RequestParts, body: Body) -> Result<Self> {
-        let body_bytes = body.collect().await.map_err(|_| Error::NotFound)?.to_bytes();
-        String::from_utf8(body_bytes.to_vec()).map_err(|_| Error::NotFound)
+impl<S> FromRequest<S> for String
+where
+    S: Send + Sync,
+{
+    type Rejection = StringRejection;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let body_bytes = collect_body(parts, body).await?;
+        String::from_utf8(body_bytes.to_vec())
+            .map_err(|err| InvalidUtf8(format!("Request body is not valid UTF-8: {}", err)).into())
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,35 @@
+use crate::{
+    extractor::{
+        rejection::{InvalidHeader, MissingHeader, TypedHeaderRejection},
+        FromRequestParts,
+    },
+    headers::Header,
+    http_request::RequestParts,
+};
+use async_trait::async_trait;
+
+#[derive(Clone, Debug)]
+pub struct TypedHeader<H>(pub H);
+
+#[async_trait]
+impl<S, H> FromRequestParts<S> for TypedHeader<H>
+where
+    S: Send + Sync,
+    H: Header + Send,
+{
+    type Rejection = TypedHeaderRejection;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let mut values = parts.headers.get_all(H::name()).iter().peekable();
+        if values.peek().is_none() {
+            return Err(MissingHeader(format!("Missing request header `{}`", H::name())).into());
+        }
+
+        H::decode(&mut values).map(TypedHeader).map_err(|err| {
+            InvalidHeader(format!("Invalid request header `{}`: {}", H::name(), err)).into()
+        })
+    }
+}
@@ -0,0 +1,343 @@
+use crate::{
+    extractor::{Form, FromRequest, FromRequestParts, Json, Path, Query},
+    http_request::{Body, RequestParts},
+    response::IntoResponse,
+    Response,
+};
+use async_trait::async_trait;
+use http::{header::CONTENT_TYPE, HeaderValue, StatusCode};
+use serde::Serialize;
+use std::{
+    fmt::{self, Display},
+    ops::{Bound, RangeBounds},
+};
+
+pub use regex::Regex;
+
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Validated<E>(pub E);
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+pub struct FieldValidator<'a, T: ?Sized> {
+    errors: &'a mut ValidationErrors,
+    field: &'a str,
+    value: &'a T,
+}
+
+#[derive(Debug)]
+pub enum ValidationRejection<R> {
+    Extractor(R),
+    Invalid(ValidationErrors),
+}
+
+pub trait HasLength {
+    fn length(&self) -> usize;
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field<'a, T: ?Sized>(
+        &'a mut self,
+        field: &'a str,
+        value: &'a T,
+    ) -> FieldValidator<'a, T> {
+        FieldValidator {
+            errors: self,
+            field,
+            value,
+        }
+    }
+
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &FieldError> {
+        self.errors.iter()
+    }
+
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<'a, T: ?Sized> FieldValidator<'a, T> {
+    pub fn length<R>(self, range: R) -> Self
+    where
+        T: HasLength,
+        R: RangeBounds<usize>,
+    {
+        if !range.contains(&self.value.length()) {
+            let message = format!("length must be {}", describe(&range));
+            self.errors.add(self.field, message);
+        }
+        self
+    }
+
+    pub fn range<R>(self, range: R) -> Self
+    where
+        T: PartialOrd + Display,
+        R: RangeBounds<T>,
+    {
+        if !range.contains(self.value) {
+            let message = format!("must be {}", describe(&range));
+            self.errors.add(self.field, message);
+        }
+        self
+    }
+
+    pub fn matches(self, regex: &Regex, message: &str) -> Self
+    where
+        T: AsRef<str>,
+    {
+        if !regex.is_match(self.value.as_ref()) {
+            self.errors.add(self.field, message);
+        }
+        self
+    }
+
+    pub fn check<F>(self, f: F, message: &str) -> Self
+    where
+        F: FnOnce(&T) -> bool,
+    {
+        if !f(self.value) {
+            self.errors.add(self.field, message);
+        }
+        self
+    }
+}
+
+impl HasLength for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl HasLength for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> HasLength for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> HasLength for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+fn describe<T: Display + ?Sized, R: RangeBounds<T>>(range: &R) -> String {
+    let lower = match range.start_bound() {
+        Bound::Included(start) => Some(format!("at least {}", start)),
+        Bound::Excluded(start) => Some(format!("greater than {}", start)),
+        Bound::Unbounded => None,
+    };
+    let upper = match range.end_bound() {
+        Bound::Included(end) => Some(format!("at most {}", end)),
+        Bound::Excluded(end) => Some(format!("less than {}", end)),
+        Bound::Unbounded => None,
+    };
+
+    match (range.start_bound(), range.end_bound(), lower, upper) {
+        (Bound::Included(start), Bound::Included(end), _, _) => {
+            format!("between {} and {}", start, end)
+        }
+        (_, _, Some(lower), Some(upper)) => format!("{} and {}", lower, upper),
+        (_, _, Some(bound), None) | (_, _, None, Some(bound)) => bound,
+        (_, _, None, None) => "anything".to_string(),
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors: Vec<String> = self
+            .errors
+            .iter()
+            .map(|error| format!("{}: {}", error.field, error.message))
+            .collect();
+        write!(f, "Validation failed: {}", errors.join(", "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+impl From<ValidationErrors> for Response {
+    fn from(errors: ValidationErrors) -> Self {
+        let body = serde_json::to_vec(&errors).unwrap_or_default();
+        let mut res = Response::new(body).status(StatusCode::UNPROCESSABLE_ENTITY);
+        res.inner_mut()
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        res
+    }
+}
+
+impl<R> From<ValidationRejection<R>> for Response
+where
+    R: IntoResponse,
+{
+    fn from(rejection: ValidationRejection<R>) -> Self {
+        match rejection {
+            ValidationRejection::Extractor(rejection) => rejection.into_response(),
+            ValidationRejection::Invalid(errors) => errors.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl<S, E> FromRequestParts<S> for Validated<E>
+where
+    S: Send + Sync,
+    E: FromRequestParts<S> + Validate,
+{
+    type Rejection = ValidationRejection<E::Rejection>;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let value = E::from_request_parts(parts, state)
+            .await
+            .map_err(ValidationRejection::Extractor)?;
+        value.validate().map_err(ValidationRejection::Invalid)?;
+        Ok(Validated(value))
+    }
+}
+
+#[async_trait]
+impl<S, E> FromRequest<S> for Validated<E>
+where
+    S: Send + Sync,
+    E: FromRequest<S> + Validate,
+{
+    type Rejection = ValidationRejection<E::Rejection>;
+
+    async fn from_request(
+        parts: &mut RequestParts,
+        body: Body,
+        state: &S,
+    ) -> Result<Self, Self::Rejection> {
+        let value = E::from_request(parts, body, state)
+            .await
+            .map_err(ValidationRejection::Extractor)?;
+        value.validate().map_err(ValidationRejection::Invalid)?;
+        Ok(Validated(value))
+    }
+}
+
+macro_rules! impl_validate_for_extractor {
+    ($($extractor:ident),*) => {
+        $(
+            impl<T: Validate> Validate for $extractor<T> {
+                fn validate(&self) -> Result<(), ValidationErrors> {
+                    self.0.validate()
+                }
+            }
+        )*
+    };
+}
+
+impl_validate_for_extractor!(Json, Query, Form, Path);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(errors: &ValidationErrors) -> Vec<&str> {
+        errors.iter().map(|error| error.message.as_str()).collect()
+    }
+
+    #[test]
+    fn describes_range_bounds() {
+        assert_eq!(describe(&(3..=32)), "between 3 and 32");
+        assert_eq!(describe(&(3..32)), "at least 3 and less than 32");
+        assert_eq!(describe(&(18..)), "at least 18");
+        assert_eq!(describe(&(..=10)), "at most 10");
+        assert_eq!(describe(&(..10)), "less than 10");
+        assert_eq!(
+            describe(&(Bound::Excluded(0), Bound::Included(1))),
+            "greater than 0 and at most 1"
+        );
+        assert_eq!(describe::<i32, _>(&..), "anything");
+    }
+
+    #[test]
+    fn length_counts_characters_not_bytes() {
+        let mut errors = ValidationErrors::new();
+        errors.field("name", "žluťoučký").length(..=9);
+        errors.field("name", "日本").length(3..);
+        errors.field("tags", &vec![1, 2, 3]).length(..3);
+        assert_eq!(
+            messages(&errors),
+            ["length must be at least 3", "length must be less than 3"]
+        );
+        assert_eq!(errors.iter().next().unwrap().field, "name");
+    }
+
+    #[test]
+    fn range_and_check_report_failures() {
+        let mut errors = ValidationErrors::new();
+        errors.field("age", &17).range(18..);
+        errors.field("age", &18).range(18..);
+        errors.field("score", &1.5).range(0.0..1.0);
+        errors
+            .field("email", "nobody")
+            .check(|email| email.contains('@'), "must be an email address");
+        assert_eq!(
+            messages(&errors),
+            [
+                "must be at least 18",
+                "must be at least 0 and less than 1",
+                "must be an email address"
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_checks_the_whole_pattern() {
+        let slug = Regex::new("^[a-z0-9-]+$").unwrap();
+        let mut errors = ValidationErrors::new();
+        errors
+            .field("slug", "hello-world")
+            .matches(&slug, "invalid slug");
+        errors
+            .field("slug", &"Hello World".to_string())
+            .matches(&slug, "invalid slug");
+        assert_eq!(messages(&errors), ["invalid slug"]);
+        assert!(ValidationErrors::new().into_result().is_ok());
+        assert!(errors.into_result().is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use headers::{Error, Header, HeaderName, HeaderValue, Mime};
+use http::header::ACCEPT;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accept(Vec<(Mime, f32)>);
+
+impl Accept {
+    pub fn iter(&self) -> impl Iterator<Item = &Mime> {
+        self.0
+            .iter()
+            .filter(|(_, quality)| *quality > 0.0)
+            .map(|(mime, _)| mime)
+    }
+
+    pub fn quality(&self, mime: &Mime) -> f32 {
+        self.0
+            .iter()
+            .filter(|(range, _)| matches(range, mime))
+            .max_by_key(|(range, _)| specificity(range))
+            .map_or(0.0, |(_, quality)| *quality)
+    }
+
+    pub fn accepts(&self, mime: &Mime) -> bool {
+        self.quality(mime) > 0.0
+    }
+
+    pub fn preferred<'a>(&self, candidates: &'a [Mime]) -> Option<&'a Mime> {
+        candidates
+            .iter()
+            .map(|mime| (mime, self.quality(mime)))
+            .filter(|(_, quality)| *quality > 0.0)
+            .fold(
+                None,
+                |best: Option<(&Mime, f32)>, (mime, quality)| match best {
+                    Some((_, best_quality)) if best_quality >= quality => best,
+                    _ => Some((mime, quality)),
+                },
+            )
+            .map(|(mime, _)| mime)
+    }
+}
+
+impl FromIterator<Mime> for Accept {
+    fn from_iter<I: IntoIterator<Item = Mime>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|mime| (mime, 1.0)).collect())
+    }
+}
+
+impl Header for Accept {
+    fn name() -> &'static HeaderName {
+        &ACCEPT
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let mut ranges = Vec::new();
+        for value in values {
+            let value = value.to_str().map_err(|_| Error::invalid())?;
+            for item in value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+            {
+                let (range, quality) = parse_range(item).ok_or_else(Error::invalid)?;
+                ranges.push((range, quality));
+            }
+        }
+
+        if ranges.is_empty() {
+            return Err(Error::invalid());
+        }
+
+        ranges.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        Ok(Self(ranges))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let value = self
+            .0
+            .iter()
+            .map(|(mime, quality)| {
+                if *quality < 1.0 {
+                    format!("{}; q={}", mime, quality)
+                } else {
+                    mime.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        values.extend(HeaderValue::from_str(&value).ok());
+    }
+}
+
+fn parse_range(item: &str) -> Option<(Mime, f32)> {
+    let mut params = item.split(';').map(str::trim);
+    let mut range = params.next()?.to_string();
+    let mut quality = 1.0;
+
+    for param in params {
+        match param.split_once('=') {
+            Some((name, value)) if name.trim().eq_ignore_ascii_case("q") => {
+                quality = value.trim().parse::<f32>().ok()?;
+                if !(0.0..=1.0).contains(&quality) {
+                    return None;
+                }
+            }
+            _ => {
+                range.push_str("; ");
+                range.push_str(param);
+            }
+        }
+    }
+
+    Some((range.parse().ok()?, quality))
+}
+
+fn matches(range: &Mime, mime: &Mime) -> bool {
+    (range.type_() == "*" || range.type_() == mime.type_())
+        && (range.subtype() == "*" || range.subtype() == mime.subtype())
+}
+
+fn specificity(range: &Mime) -> u8 {
+    match (range.type_() == "*", range.subtype() == "*") {
+        (true, _) => 0,
+        (false, true) => 1,
+        (false, false) => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(value: &'static str) -> Result<Accept, Error> {
+        Accept::decode(&mut [HeaderValue::from_static(value)].iter())
+    }
+
+    fn mime(value: &str) -> Mime {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_quality_values() {
+        let accept = decode("text/html, application/json;q=0.5, text/*; q=0.8").unwrap();
+        assert_eq!(accept.quality(&mime("text/html")), 1.0);
+        assert_eq!(accept.quality(&mime("application/json")), 0.5);
+        assert_eq!(accept.quality(&mime("text/plain")), 0.8);
+        assert_eq!(accept.quality(&mime("image/png")), 0.0);
+    }
+
+    #[test]
+    fn orders_ranges_by_quality() {
+        let accept = decode("text/plain;q=0.2, application/json;q=0.9, text/html").unwrap();
+        let order: Vec<String> = accept.iter().map(ToString::to_string).collect();
+        assert_eq!(order, ["text/html", "application/json", "text/plain"]);
+    }
+
+    #[test]
+    fn rejects_out_of_range_or_malformed_quality() {
+        for value in [
+            "text/html;q=1.5",
+            "text/html;q=-0.1",
+            "text/html;q=abc",
+            "text/html;q=NaN",
+            "text/html;q=",
+            "not a mime",
+            "",
+            " , ",
+        ] {
+            assert!(decode(value).is_err(), "{}", value);
+        }
+        assert!(decode("text/html;q=0").is_ok());
+        assert!(decode("text/html;q=1").is_ok());
+        assert!(decode("text/html;Q=0.5").is_ok());
+    }
+
+    #[test]
+    fn zero_quality_excludes_a_type() {
+        let accept = decode("application/json, text/html;q=0").unwrap();
+        assert!(!accept.accepts(&mime("text/html")));
+        assert!(accept.accepts(&mime("application/json")));
+        assert_eq!(accept.iter().count(), 1);
+    }
+
+    #[test]
+    fn most_specific_range_wins() {
+        let accept = decode("text/html;q=0, */*").unwrap();
+        assert!(!accept.accepts(&mime("text/html")));
+        assert!(accept.accepts(&mime("text/plain")));
+        assert!(accept.accepts(&mime("image/png")));
+
+        let accept = decode("*/*;q=0.1, text/*;q=0.5, text/html").unwrap();
+        assert_eq!(accept.quality(&mime("text/html")), 1.0);
+        assert_eq!(accept.quality(&mime("text/css")), 0.5);
+        assert_eq!(accept.quality(&mime("image/png")), 0.1);
+    }
+
+    #[test]
+    fn preferred_picks_highest_quality_then_candidate_order() {
+        let candidates = [mime("text/html"), mime("application/json")];
+
+        let accept = decode("text/html;q=0.5, application/json").unwrap();
+        assert_eq!(accept.preferred(&candidates), Some(&candidates[1]));
+
+        let accept = decode("*/*").unwrap();
+        assert_eq!(accept.preferred(&candidates), Some(&candidates[0]));
+
+        let accept = decode("text/html;q=0, application/json;q=0").unwrap();
+        assert_eq!(accept.preferred(&candidates), None);
+
+        let accept = decode("image/png").unwrap();
+        assert_eq!(accept.preferred(&candidates), None);
+    }
+
+    #[test]
+    fn merges_multiple_header_values() {
+        let values = [
+            HeaderValue::from_static("text/html;q=0.5"),
+            HeaderValue::from_static("application/json"),
+        ];
+        let accept = Accept::decode(&mut values.iter()).unwrap();
+        assert_eq!(accept.quality(&mime("text/html")), 0.5);
+        assert_eq!(accept.quality(&mime("application/json")), 1.0);
+    }
+
+    #[test]
+    fn encode_round_trips() {
+        let accept = decode("text/html;level=1, application/json;q=0.5, */*;q=0").unwrap();
+
+        let mut values = Vec::new();
+        accept.encode(&mut values);
+        assert_eq!(
+            values,
+            ["text/html; level=1, application/json; q=0.5, */*; q=0"]
+        );
+        assert_eq!(Accept::decode(&mut values.iter()).unwrap(), accept);
+
+        let accept: Accept = [mime("text/html"), mime("application/json")]
+            .into_iter()
+            .collect();
+        let mut values = Vec::new();
+        accept.encode(&mut values);
+        assert_eq!(values, ["text/html, application/json"]);
+    }
+}
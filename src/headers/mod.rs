@@ -0,0 +1,4 @@
+mod accept;
+
+pub use self::accept::Accept;
+pub use headers::*;
@@ -1,22 +1,54 @@
-use http_body_util::BodyExt;
-use hyper::body::Incoming;
-use hyper::{HeaderMap, Method, Uri};
+use crate::router::segments;
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Frame, Incoming, SizeHint};
+use hyper::{http::Extensions, HeaderMap, Method, Uri, Version};
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 pub struct Request {
-    inner: hyper::Request<Incoming>,
+    inner: hyper::Request<Body>,
+    params: PathParams,
 }
 
 pub struct RequestParts {
     pub method: Method,
     pub uri: Uri,
+    pub version: Version,
     pub headers: HeaderMap,
+    pub params: PathParams,
+    pub extensions: Extensions,
 }
 
-pub type Body = hyper::body::Incoming;
+#[derive(Clone, Debug, Default)]
+pub struct PathParams {
+    route: Option<Arc<str>>,
+    uri: Uri,
+}
+
+pub struct Body {
+    kind: BodyKind,
+}
+
+enum BodyKind {
+    Incoming(Incoming),
+    Full(Full<Bytes>),
+}
 
 impl Request {
+    pub fn new<B: Into<Body>>(req: hyper::Request<B>) -> Self {
+        Self {
+            inner: req.map(Into::into),
+            params: PathParams::default(),
+        }
+    }
+
     pub fn from_hyper(req: hyper::Request<Incoming>) -> Self {
-        Self { inner: req }
+        Self::new(req)
     }
 
     pub fn into_parts(self) -> (RequestParts, Body) {
@@ -24,20 +56,169 @@ impl Request {
         let request_parts = RequestParts {
             method: parts.method,
             uri: parts.uri,
+            version: parts.version,
             headers: parts.headers,
+            params: self.params,
+            extensions: parts.extensions,
         };
         (request_parts, body)
     }
 
-    pub fn inner(&self) -> &hyper::Request<Incoming> {
+    pub fn inner(&self) -> &hyper::Request<Body> {
         &self.inner
     }
 
-    pub fn inner_mut(&mut self) -> &mut hyper::Request<Incoming> {
+    pub fn inner_mut(&mut self) -> &mut hyper::Request<Body> {
         &mut self.inner
     }
 
+    pub fn extensions(&self) -> &Extensions {
+        self.inner.extensions()
+    }
+
+    pub fn extensions_mut(&mut self) -> &mut Extensions {
+        self.inner.extensions_mut()
+    }
+
+    pub fn params(&self) -> Option<&PathParams> {
+        (!self.params.is_empty()).then_some(&self.params)
+    }
+
+    pub(crate) fn set_route(&mut self, route: &Arc<str>) {
+        self.params = PathParams::new(route.clone(), self.inner.uri().clone());
+    }
+
     pub async fn body_bytes(self) -> Result<bytes::Bytes, hyper::Error> {
-        self.inner.into_body().collect().await.map(|body| body.to_bytes())
+        self.inner
+            .into_body()
+            .collect()
+            .await
+            .map(|body| body.to_bytes())
+    }
+}
+
+impl PathParams {
+    pub(crate) fn new(route: Arc<str>, uri: Uri) -> Self {
+        Self {
+            route: Some(route),
+            uri,
+        }
+    }
+
+    pub(crate) fn route(&self) -> Option<&Arc<str>> {
+        self.route.as_ref()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        let mut rest = Some(self.uri.path().strip_prefix('/').unwrap_or(self.uri.path()));
+        segments(self.route.as_deref().unwrap_or_default()).filter_map(move |segment| {
+            let path = rest?;
+            if let Some(name) = segment.strip_prefix('*') {
+                rest = None;
+                return Some((name, path));
+            }
+            let value = match path.split_once('/') {
+                Some((value, tail)) => {
+                    rest = Some(tail);
+                    value
+                }
+                None => {
+                    rest = None;
+                    path
+                }
+            };
+            segment.strip_prefix(':').map(|name| (name, value))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Body {
+    pub fn empty() -> Self {
+        Self::from(Bytes::new())
+    }
+}
+
+impl Default for Body {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl From<Incoming> for Body {
+    fn from(body: Incoming) -> Self {
+        Self {
+            kind: BodyKind::Incoming(body),
+        }
     }
-} 
\ No newline at end of file
+}
+
+impl From<Bytes> for Body {
+    fn from(body: Bytes) -> Self {
+        Self {
+            kind: BodyKind::Full(Full::new(body)),
+        }
+    }
+}
+
+impl From<&'static str> for Body {
+    fn from(body: &'static str) -> Self {
+        Self::from(Bytes::from_static(body.as_bytes()))
+    }
+}
+
+impl From<String> for Body {
+    fn from(body: String) -> Self {
+        Self::from(Bytes::from(body))
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(body: Vec<u8>) -> Self {
+        Self::from(Bytes::from(body))
+    }
+}
+
+impl hyper::body::Body for Body {
+    type Data = Bytes;
+    type Error = hyper::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, hyper::Error>>> {
+        match &mut self.get_mut().kind {
+            BodyKind::Incoming(body) => Pin::new(body).poll_frame(cx),
+            BodyKind::Full(body) => Pin::new(body)
+                .poll_frame(cx)
+                .map_err(|never: Infallible| match never {}),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match &self.kind {
+            BodyKind::Incoming(body) => body.is_end_stream(),
+            BodyKind::Full(body) => body.is_end_stream(),
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match &self.kind {
+            BodyKind::Incoming(body) => body.size_hint(),
+            BodyKind::Full(body) => body.size_hint(),
+        }
+    }
+}
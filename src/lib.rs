@@ -1,21 +1,29 @@
 pub mod error;
 pub mod extractor;
+pub mod headers;
 pub mod http_request;
 pub mod middleware;
 pub mod response;
 pub mod router;
 pub mod server;
 pub mod service;
+pub mod session;
 pub mod ws;
 
 pub use self::{
     error::{Error, Result},
-    extractor::{FromBody, FromRequest, Json},
+    extractor::{
+        BodyStream, ConnectInfo, ConnectionInfo, CookieJar, Extension, Form, FromRef, FromRequest,
+        FromRequestParts, Json, MatchedPath, Multipart, Path, PrivateCookieJar, Query,
+        SignedCookieJar, State, TypedHeader, Validate, Validated, ValidationErrors,
+    },
     http_request::Request,
-    middleware::{Layer, LogLayer, Stack},
-    response::Response,
-    router::Router,
+    middleware::{AddExtensionLayer, Layer, LogLayer, Stack},
+    response::{IntoResponse, Response},
+    router::{OriginalUri, Router},
     server::Server,
     service::{service_fn, Service},
+    session::{Session, SessionLayer},
     ws::{Message, WebSocket},
-}; 
\ No newline at end of file
+};
+
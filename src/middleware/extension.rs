@@ -0,0 +1,47 @@
+use crate::{http_request::Request, middleware::Layer, service::Service};
+
+#[derive(Clone)]
+pub struct AddExtensionLayer<T> {
+    value: T,
+}
+
+impl<T> AddExtensionLayer<T> {
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+}
+
+impl<S, T> Layer<S> for AddExtensionLayer<T>
+where
+    T: Clone,
+{
+    type Service = AddExtension<S, T>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AddExtension {
+            inner,
+            value: self.value.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AddExtension<S, T> {
+    inner: S,
+    value: T,
+}
+
+impl<S, T> Service<Request> for AddExtension<S, T>
+where
+    S: Service<Request>,
+    T: Clone + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn call(&self, mut req: Request) -> Self::Future {
+        req.extensions_mut().insert(self.value.clone());
+        self.inner.call(req)
+    }
+}
@@ -1,5 +1,7 @@
+pub mod extension;
 pub mod logger;
 
+pub use self::extension::AddExtensionLayer;
 pub use self::logger::LogLayer;
 use crate::service::Service;
 use std::sync::Arc;
@@ -11,30 +13,42 @@ pub trait Layer<S> {
 }
 
 #[derive(Clone)]
-pub struct Stack<L, S> {
-    layer: L,
+pub struct Stack<S> {
     service: S,
 }
 
-impl<L, S> Stack<L, S> {
-    pub fn new(layer: L, service: S) -> Self {
-        Self { layer, service }
+impl<S> Stack<S> {
+    pub fn new<L, T>(layer: L, inner: T) -> Self
+    where
+        L: Layer<T, Service = S>,
+    {
+        Self {
+            service: layer.layer(inner),
+        }
+    }
+
+    pub fn layer<L>(self, layer: L) -> Stack<L::Service>
+    where
+        L: Layer<S>,
+    {
+        Stack::new(layer, self.service)
+    }
+
+    pub fn into_inner(self) -> S {
+        self.service
     }
 }
 
-impl<L, S, Req> Service<Req> for Stack<L, S>
+impl<S, Req> Service<Req> for Stack<S>
 where
-    L: Layer<S>,
-    S: Clone,
-    L::Service: Service<Req>,
+    S: Service<Req>,
 {
-    type Response = <L::Service as Service<Req>>::Response;
-    type Error = <L::Service as Service<Req>>::Error;
-    type Future = <L::Service as Service<Req>>::Future;
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
 
     fn call(&self, req: Req) -> Self::Future {
-        let s = self.layer.layer(self.service.clone());
-        s.call(req)
+        self.service.call(req)
     }
 }
 
@@ -56,4 +70,4 @@ where
     fn layer(&self, _inner: S) -> Self::Service {
         self.0.clone()
     }
-} 
\ No newline at end of file
+}
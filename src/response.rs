@@ -1,13 +1,30 @@
+use crate::headers::{Header, HeaderMapExt};
+use crate::Result;
+use http::header::IntoHeaderName;
 use http::Response as HttpResponse;
 use http_body_util::Full;
 use hyper::body::Bytes;
-use hyper::header::HeaderValue;
+use hyper::header::{HeaderName, HeaderValue};
 use hyper::StatusCode;
+use std::convert::Infallible;
 
 pub struct Response {
     inner: HttpResponse<Full<Bytes>>,
 }
 
+pub trait IntoResponse {
+    fn into_response(self) -> Response;
+}
+
+impl<T> IntoResponse for T
+where
+    T: Into<Response>,
+{
+    fn into_response(self) -> Response {
+        self.into()
+    }
+}
+
 impl Response {
     pub fn new<T: Into<Bytes>>(body: T) -> Self {
         Self {
@@ -20,13 +37,49 @@ impl Response {
         self
     }
 
-    pub fn header(mut self, key: &'static str, value: &'static str) -> Self {
+    pub fn header<K: IntoHeaderName>(mut self, key: K, value: &'static str) -> Self {
         self.inner
             .headers_mut()
             .insert(key, HeaderValue::from_static(value));
         self
     }
 
+    pub fn append_header<K: IntoHeaderName>(mut self, key: K, value: &'static str) -> Self {
+        self.inner
+            .headers_mut()
+            .append(key, HeaderValue::from_static(value));
+        self
+    }
+
+    pub fn try_header<K, V>(mut self, key: K, value: V) -> Result<Self>
+    where
+        K: TryInto<HeaderName>,
+        K::Error: Into<http::Error>,
+        V: TryInto<HeaderValue>,
+        V::Error: Into<http::Error>,
+    {
+        let (key, value) = header_pair(key, value)?;
+        self.inner.headers_mut().insert(key, value);
+        Ok(self)
+    }
+
+    pub fn try_append_header<K, V>(mut self, key: K, value: V) -> Result<Self>
+    where
+        K: TryInto<HeaderName>,
+        K::Error: Into<http::Error>,
+        V: TryInto<HeaderValue>,
+        V::Error: Into<http::Error>,
+    {
+        let (key, value) = header_pair(key, value)?;
+        self.inner.headers_mut().append(key, value);
+        Ok(self)
+    }
+
+    pub fn typed_header<H: Header>(mut self, header: H) -> Self {
+        self.inner.headers_mut().typed_insert(header);
+        self
+    }
+
     pub fn into_hyper(self) -> HttpResponse<Full<Bytes>> {
         self.inner
     }
@@ -52,4 +105,72 @@ impl From<Bytes> for Response {
     fn from(body: Bytes) -> Self {
         Response::new(body)
     }
-} 
\ No newline at end of file
+}
+
+impl From<Infallible> for Response {
+    fn from(never: Infallible) -> Self {
+        match never {}
+    }
+}
+
+fn header_pair<K, V>(key: K, value: V) -> Result<(HeaderName, HeaderValue)>
+where
+    K: TryInto<HeaderName>,
+    K::Error: Into<http::Error>,
+    V: TryInto<HeaderValue>,
+    V::Error: Into<http::Error>,
+{
+    let key = key.try_into().map_err(Into::into)?;
+    let value = value.try_into().map_err(Into::into)?;
+    Ok((key, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use http::header;
+
+    #[test]
+    fn try_header_sets_owned_values() {
+        let response = Response::new("")
+            .try_header(header::LOCATION, format!("/resources/{}", 42))
+            .unwrap()
+            .try_append_header("x-tag", "a")
+            .unwrap()
+            .try_append_header("x-tag", String::from("b"))
+            .unwrap();
+        let headers = response.into_hyper().into_parts().0.headers;
+        assert_eq!(headers[header::LOCATION], "/resources/42");
+        assert_eq!(
+            headers.get_all("x-tag").iter().collect::<Vec<_>>(),
+            ["a", "b"]
+        );
+    }
+
+    #[test]
+    fn try_header_reports_invalid_values() {
+        let err = Response::new("").try_header("x-name", "line\nbreak").err();
+        assert!(matches!(err, Some(Error::Http(_))));
+        let err = Response::new("").try_header("bad name", "value").err();
+        assert!(matches!(err, Some(Error::Http(_))));
+    }
+
+    #[test]
+    fn header_replaces_and_append_header_adds() {
+        let response = Response::new("")
+            .header(header::CONTENT_TYPE, "text/plain")
+            .header("content-type", "text/html")
+            .append_header("vary", "accept")
+            .append_header("vary", "cookie");
+        let headers = response.into_hyper().into_parts().0.headers;
+        assert_eq!(headers[header::CONTENT_TYPE], "text/html");
+        assert_eq!(headers.get_all("vary").iter().count(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn header_panics_on_invalid_static_value() {
+        let _ = Response::new("").header("x-name", "line\nbreak");
+    }
+}
@@ -0,0 +1,80 @@
+use crate::{
+    extractor::{FromRequest, FromRequestParts},
+    response::IntoResponse,
+    Request, Response, Result,
+};
+use async_trait::async_trait;
+use std::future::Future;
+
+#[async_trait]
+pub trait Handler<Args, S>: Clone + Send + Sync + 'static {
+    async fn call(self, req: Request, state: S) -> Result<Response>;
+}
+
+#[async_trait]
+impl<F, Fut, S> Handler<(), S> for F
+where
+    F: Fn() -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<Response>> + Send,
+    S: Send + 'static,
+{
+    async fn call(self, _req: Request, _state: S) -> Result<Response> {
+        self().await
+    }
+}
+
+macro_rules! impl_handler {
+    ([$($ty:ident),*], $last:ident) => {
+        #[async_trait]
+        #[allow(non_snake_case)]
+        impl<F, Fut, S, M, $($ty,)* $last> Handler<(M, $($ty,)* $last,), S> for F
+        where
+            F: Fn($($ty,)* $last) -> Fut + Clone + Send + Sync + 'static,
+            Fut: Future<Output = Result<Response>> + Send,
+            S: Send + Sync + 'static,
+            $($ty: FromRequestParts<S> + Send,)*
+            $last: FromRequest<S, M> + Send,
+        {
+            async fn call(self, req: Request, state: S) -> Result<Response> {
+                let (mut parts, body) = req.into_parts();
+                $(
+                    let $ty = match $ty::from_request_parts(&mut parts, &state).await {
+                        Ok(value) => value,
+                        Err(rejection) => return Ok(rejection.into_response()),
+                    };
+                )*
+                let $last = match $last::from_request(&mut parts, body, &state).await {
+                    Ok(value) => value,
+                    Err(rejection) => return Ok(rejection.into_response()),
+                };
+                self($($ty,)* $last).await
+            }
+        }
+    };
+}
+
+impl_handler!([], T1);
+impl_handler!([T1], T2);
+impl_handler!([T1, T2], T3);
+impl_handler!([T1, T2, T3], T4);
+impl_handler!([T1, T2, T3, T4], T5);
+impl_handler!([T1, T2, T3, T4, T5], T6);
+impl_handler!([T1, T2, T3, T4, T5, T6], T7);
+impl_handler!([T1, T2, T3, T4, T5, T6, T7], T8);
+impl_handler!([T1, T2, T3, T4, T5, T6, T7, T8], T9);
+impl_handler!([T1, T2, T3, T4, T5, T6, T7, T8, T9], T10);
+impl_handler!([T1, T2, T3, T4, T5, T6, T7, T8, T9, T10], T11);
+impl_handler!([T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11], T12);
+impl_handler!([T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12], T13);
+impl_handler!(
+    [T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13],
+    T14
+);
+impl_handler!(
+    [T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14],
+    T15
+);
+impl_handler!(
+    [T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15],
+    T16
+);
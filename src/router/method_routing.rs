@@ -0,0 +1,240 @@
+use crate::router::{BoxedHandler, Handler};
+use crate::{extractor::body::BodyLimit, Request, Response};
+use http::{header::ALLOW, HeaderValue, Method, StatusCode};
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct MethodRouter<S = ()> {
+    handlers: Vec<(Method, BoxedHandler<S>)>,
+    any: Option<BoxedHandler<S>>,
+    body_limit: Option<usize>,
+}
+
+macro_rules! method_fns {
+    ($($name:ident => $method:ident,)*) => {
+        $(
+            pub fn $name<F, Args, S>(handler: F) -> MethodRouter<S>
+            where
+                F: Handler<Args, S>,
+                Args: Send + 'static,
+                S: Clone + Send + Sync + 'static,
+            {
+                MethodRouter::new().on(Method::$method, handler)
+            }
+        )*
+
+        impl<S> MethodRouter<S>
+        where
+            S: Clone + Send + Sync + 'static,
+        {
+            $(
+                pub fn $name<F, Args>(self, handler: F) -> Self
+                where
+                    F: Handler<Args, S>,
+                    Args: Send + 'static,
+                {
+                    self.on(Method::$method, handler)
+                }
+            )*
+        }
+    };
+}
+
+method_fns! {
+    get => GET,
+    post => POST,
+    put => PUT,
+    patch => PATCH,
+    delete => DELETE,
+    head => HEAD,
+    options => OPTIONS,
+    trace => TRACE,
+}
+
+pub fn on<F, Args, S>(method: Method, handler: F) -> MethodRouter<S>
+where
+    F: Handler<Args, S>,
+    Args: Send + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    MethodRouter::new().on(method, handler)
+}
+
+pub fn any<F, Args, S>(handler: F) -> MethodRouter<S>
+where
+    F: Handler<Args, S>,
+    Args: Send + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    MethodRouter::new().any(handler)
+}
+
+impl<S> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            handlers: Vec::new(),
+            any: None,
+            body_limit: None,
+        }
+    }
+
+    pub fn on<F, Args>(mut self, method: Method, handler: F) -> Self
+    where
+        F: Handler<Args, S>,
+        Args: Send + 'static,
+    {
+        if let Err(conflict) = self.insert(method, boxed(handler)) {
+            panic!(
+                "{} handler registered twice on the same MethodRouter",
+                conflict
+            );
+        }
+        self
+    }
+
+    pub fn any<F, Args>(mut self, handler: F) -> Self
+    where
+        F: Handler<Args, S>,
+        Args: Send + 'static,
+    {
+        if let Err(conflict) = self.set_any(boxed(handler)) {
+            panic!(
+                "{} handler registered twice on the same MethodRouter",
+                conflict
+            );
+        }
+        self
+    }
+
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.body_limit = Some(limit);
+        self
+    }
+
+    pub(crate) fn merge(&mut self, other: MethodRouter<S>) -> Result<(), String> {
+        for (method, handler) in other.handlers {
+            self.insert(method, handler)?;
+        }
+        if let Some(handler) = other.any {
+            self.set_any(handler)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn map<S2, F>(self, f: F) -> MethodRouter<S2>
+    where
+        F: Fn(BoxedHandler<S>) -> BoxedHandler<S2>,
+    {
+        MethodRouter {
+            handlers: self
+                .handlers
+                .into_iter()
+                .map(|(method, handler)| (method, f(handler)))
+                .collect(),
+            any: self.any.map(&f),
+            body_limit: self.body_limit,
+        }
+    }
+
+    pub(crate) fn apply_body_limit(mut self) -> Self {
+        match self.body_limit.take() {
+            Some(limit) => self.map(|handler| with_body_limit(handler, limit)),
+            None => self,
+        }
+    }
+
+    pub(crate) fn handler(&self, method: &Method) -> Option<&BoxedHandler<S>> {
+        self.find(method)
+            .or_else(|| {
+                if method == Method::HEAD {
+                    self.find(&Method::GET)
+                } else {
+                    None
+                }
+            })
+            .or(self.any.as_ref())
+    }
+
+    pub(crate) fn reject(&self, method: &Method) -> Response {
+        let status = if method == Method::OPTIONS {
+            StatusCode::NO_CONTENT
+        } else {
+            StatusCode::METHOD_NOT_ALLOWED
+        };
+        let mut res = Response::new("").status(status);
+        res.inner_mut().headers_mut().insert(ALLOW, self.allow());
+        res
+    }
+
+    fn allow(&self) -> HeaderValue {
+        let mut methods: Vec<&str> = self.handlers.iter().map(|(m, _)| m.as_str()).collect();
+        if self.find(&Method::GET).is_some() && self.find(&Method::HEAD).is_none() {
+            methods.push(Method::HEAD.as_str());
+        }
+        if self.find(&Method::OPTIONS).is_none() {
+            methods.push(Method::OPTIONS.as_str());
+        }
+        HeaderValue::from_str(&methods.join(", ")).expect("method names are valid header values")
+    }
+
+    fn find(&self, method: &Method) -> Option<&BoxedHandler<S>> {
+        self.handlers
+            .iter()
+            .find(|(m, _)| m == method)
+            .map(|(_, handler)| handler)
+    }
+
+    fn insert(&mut self, method: Method, handler: BoxedHandler<S>) -> Result<(), String> {
+        if self.find(&method).is_some() {
+            return Err(method.to_string());
+        }
+        self.handlers.push((method, handler));
+        Ok(())
+    }
+
+    fn set_any(&mut self, handler: BoxedHandler<S>) -> Result<(), String> {
+        if self.any.is_some() {
+            return Err("`any`".to_string());
+        }
+        self.any = Some(handler);
+        Ok(())
+    }
+}
+
+impl<S> Default for MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn boxed<F, Args, S>(handler: F) -> BoxedHandler<S>
+where
+    F: Handler<Args, S>,
+    Args: Send + 'static,
+    S: Send + 'static,
+{
+    Arc::new(move |req: Request, state: S| handler.clone().call(req, state))
+}
+
+pub(crate) fn with_body_limit<S>(handler: BoxedHandler<S>, limit: usize) -> BoxedHandler<S>
+where
+    S: 'static,
+{
+    Arc::new(move |mut req: Request, state: S| {
+        req.extensions_mut().insert(BodyLimit(limit));
+        handler(req, state)
+    })
+}
+
+pub(crate) fn with_state<S, S2>(handler: BoxedHandler<S>, state: S) -> BoxedHandler<S2>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Arc::new(move |req: Request, _: S2| handler(req, state.clone()))
+}
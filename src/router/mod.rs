@@ -0,0 +1,304 @@
+use crate::{
+    extractor::body::BodyLimit,
+    middleware::{Layer, Stack},
+    ws::upgrade::upgrade as ws_upgrade,
+    Error, Request, Response, Result, Service,
+};
+use http::Method;
+use std::{
+    future::{self, Future},
+    marker::Send,
+    pin::Pin,
+    sync::Arc,
+};
+
+mod handler;
+mod method_routing;
+mod nest;
+mod node;
+
+pub use self::handler::Handler;
+pub use self::method_routing::{
+    any, delete, get, head, on, options, patch, post, put, trace, MethodRouter,
+};
+pub use self::nest::OriginalUri;
+pub(crate) use self::node::segments;
+use self::node::{Match, Node};
+
+pub(crate) type BoxedHandler<S> =
+    Arc<dyn Fn(Request, S) -> Pin<Box<dyn Future<Output = Result<Response>> + Send>> + Send + Sync>;
+pub(crate) type WsHandler = Box<dyn Fn(Request) -> Result<Response> + Send + Sync>;
+
+const FALLBACK_PATH: &str = "/*fallback";
+
+#[derive(Clone)]
+pub struct Router<S = ()> {
+    table: Arc<RouteTable<S>>,
+}
+
+#[derive(Clone)]
+struct RouteTable<S> {
+    routes: Node<MethodRouter<S>>,
+    ws_routes: Node<Arc<WsHandler>>,
+    fallbacks: Node<BoxedHandler<S>>,
+    body_limit: Option<usize>,
+}
+
+impl<S> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            table: Arc::new(RouteTable {
+                routes: Node::new(),
+                ws_routes: Node::new(),
+                fallbacks: Node::new(),
+                body_limit: None,
+            }),
+        }
+    }
+
+    pub fn route(mut self, path: &str, method_router: MethodRouter<S>) -> Self {
+        let merged = self
+            .table_mut()
+            .routes
+            .entry(path)
+            .get_or_insert_with(MethodRouter::new)
+            .merge(method_router.apply_body_limit());
+        if let Err(conflict) = merged {
+            panic!(
+                "overlapping route: {} `{}` is already registered",
+                conflict, path
+            );
+        }
+        self
+    }
+
+    pub fn on<F, Args>(self, method: Method, path: &str, handler: F) -> Self
+    where
+        F: Handler<Args, S>,
+        Args: Send + 'static,
+    {
+        self.route(path, on(method, handler))
+    }
+
+    pub fn any<F, Args>(self, path: &str, handler: F) -> Self
+    where
+        F: Handler<Args, S>,
+        Args: Send + 'static,
+    {
+        self.route(path, any(handler))
+    }
+
+    pub fn nest(mut self, prefix: &str, router: Router<S>) -> Self {
+        let depth = nest::depth(prefix);
+        let table = router.into_table();
+
+        let limit = table.body_limit;
+
+        for (path, method_router) in table.routes.into_entries() {
+            let method_router =
+                method_router.map(|handler| nest::nested(limited(handler, limit), depth));
+            self = self.route(&nest::join(prefix, &path), method_router);
+        }
+
+        for (path, handler) in table.ws_routes.into_entries() {
+            self = self.ws_route(&nest::join(prefix, &path), nest::nested_ws(handler, depth));
+        }
+
+        for (path, handler) in table.fallbacks.into_entries() {
+            let handler = nest::nested(limited(handler, limit), depth);
+            self = self.fallback_route(&nest::join(prefix, &path), handler);
+        }
+
+        self
+    }
+
+    pub fn merge(mut self, other: Router<S>) -> Self {
+        let table = other.into_table();
+        let limit = table.body_limit;
+
+        for (path, method_router) in table.routes.into_entries() {
+            self = self.route(&path, method_router.map(|handler| limited(handler, limit)));
+        }
+
+        for (path, handler) in table.ws_routes.into_entries() {
+            self = self.ws_route(&path, handler);
+        }
+
+        for (path, handler) in table.fallbacks.into_entries() {
+            self = self.fallback_route(&path, limited(handler, limit));
+        }
+
+        self
+    }
+
+    pub fn body_limit(mut self, limit: usize) -> Self {
+        self.table_mut().body_limit = Some(limit);
+        self
+    }
+
+    pub fn fallback<F, Args>(mut self, handler: F) -> Self
+    where
+        F: Handler<Args, S>,
+        Args: Send + 'static,
+    {
+        let handler = method_routing::boxed(handler);
+        let table = self.table_mut();
+        *table.fallbacks.entry("/") = Some(handler.clone());
+        *table.fallbacks.entry(FALLBACK_PATH) = Some(handler);
+        self
+    }
+
+    fn fallback_route(mut self, path: &str, handler: BoxedHandler<S>) -> Self {
+        let slot = self.table_mut().fallbacks.entry(path);
+        assert!(
+            slot.is_none(),
+            "overlapping fallback: `{}` already has a fallback handler",
+            path
+        );
+        *slot = Some(handler);
+        self
+    }
+
+    pub fn ws<F, Fut>(self, path: &str, f: F) -> Self
+    where
+        F: Fn(crate::ws::WebSocket) -> Fut + Send + Sync + 'static + Clone,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.ws_route(path, Arc::new(Box::new(ws_upgrade(f))))
+    }
+
+    fn ws_route(mut self, path: &str, handler: Arc<WsHandler>) -> Self {
+        let slot = self.table_mut().ws_routes.entry(path);
+        assert!(
+            slot.is_none(),
+            "overlapping route: websocket `{}` is already registered",
+            path
+        );
+        *slot = Some(handler);
+        self
+    }
+
+    pub fn with_state<S2>(self, state: S) -> Router<S2>
+    where
+        S2: Clone + Send + Sync + 'static,
+    {
+        let table = self.into_table();
+        let mut router = Router::new();
+        router.table_mut().body_limit = table.body_limit;
+
+        for (path, method_router) in table.routes.into_entries() {
+            let method_router =
+                method_router.map(|handler| method_routing::with_state(handler, state.clone()));
+            router = router.route(&path, method_router);
+        }
+
+        for (path, handler) in table.ws_routes.into_entries() {
+            router = router.ws_route(&path, handler);
+        }
+
+        for (path, handler) in table.fallbacks.into_entries() {
+            let handler = method_routing::with_state(handler, state.clone());
+            router = router.fallback_route(&path, handler);
+        }
+
+        router
+    }
+
+    pub fn layer<L>(self, layer: L) -> Stack<L::Service>
+    where
+        L: Layer<Self>,
+    {
+        Stack::new(layer, self)
+    }
+
+    fn table_mut(&mut self) -> &mut RouteTable<S> {
+        Arc::make_mut(&mut self.table)
+    }
+
+    fn into_table(self) -> RouteTable<S> {
+        Arc::try_unwrap(self.table).unwrap_or_else(|table| (*table).clone())
+    }
+}
+
+macro_rules! router_method_fns {
+    ($($name:ident => $method:ident,)*) => {
+        impl<S> Router<S>
+        where
+            S: Clone + Send + Sync + 'static,
+        {
+            $(
+                pub fn $name<F, Args>(self, path: &str, handler: F) -> Self
+                where
+                    F: Handler<Args, S>,
+                    Args: Send + 'static,
+                {
+                    self.on(Method::$method, path, handler)
+                }
+            )*
+        }
+    };
+}
+
+router_method_fns! {
+    get => GET,
+    post => POST,
+    put => PUT,
+    patch => PATCH,
+    delete => DELETE,
+    head => HEAD,
+    options => OPTIONS,
+    trace => TRACE,
+}
+
+impl<S> Default for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Service<Request> for Router {
+    type Response = Response;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response>> + Send>>;
+
+    fn call(&self, mut req: Request) -> Self::Future {
+        if let Some(limit) = self.table.body_limit {
+            req.inner_mut().extensions_mut().insert(BodyLimit(limit));
+        }
+
+        if let Some(Match { value, route }) = self.table.ws_routes.at(req.inner().uri().path()) {
+            req.set_route(route);
+            return Box::pin(future::ready(value(req)));
+        }
+
+        if let Some(Match { value, route }) = self.table.routes.at(req.inner().uri().path()) {
+            let Some(handler) = value.handler(req.inner().method()) else {
+                return Box::pin(future::ready(Ok(value.reject(req.inner().method()))));
+            };
+            req.set_route(route);
+            return handler(req, ());
+        }
+
+        if let Some(Match { value, .. }) = self.table.fallbacks.at(req.inner().uri().path()) {
+            return value(req, ());
+        }
+
+        Box::pin(future::ready(Err(Error::NotFound)))
+    }
+}
+
+fn limited<S>(handler: BoxedHandler<S>, limit: Option<usize>) -> BoxedHandler<S>
+where
+    S: 'static,
+{
+    match limit {
+        Some(limit) => method_routing::with_body_limit(handler, limit),
+        None => handler,
+    }
+}
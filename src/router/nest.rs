@@ -0,0 +1,72 @@
+use crate::router::{node::segments, BoxedHandler, WsHandler};
+use crate::Request;
+use http::uri::{PathAndQuery, Uri};
+use std::sync::Arc;
+
+#[derive(Clone, Debug)]
+pub struct OriginalUri(pub Uri);
+
+pub(crate) fn join(prefix: &str, path: &str) -> String {
+    let prefix = prefix.trim_end_matches('/');
+    if path == "/" {
+        prefix.to_string()
+    } else {
+        format!("{}{}", prefix, path)
+    }
+}
+
+pub(crate) fn depth(prefix: &str) -> usize {
+    let depth = segments(prefix.trim_end_matches('/'))
+        .inspect(|segment| {
+            assert!(
+                !segment.starts_with('*'),
+                "nest prefix `{}` cannot contain a catch-all segment",
+                prefix
+            )
+        })
+        .count();
+    assert!(
+        depth > 0,
+        "cannot nest at the root, use `Router::merge` instead"
+    );
+    depth
+}
+
+pub(crate) fn nested<S>(handler: BoxedHandler<S>, depth: usize) -> BoxedHandler<S>
+where
+    S: 'static,
+{
+    Arc::new(move |mut req: Request, state: S| {
+        strip_prefix(&mut req, depth);
+        handler(req, state)
+    })
+}
+
+pub(crate) fn nested_ws(handler: Arc<WsHandler>, depth: usize) -> Arc<WsHandler> {
+    Arc::new(Box::new(move |mut req: Request| {
+        strip_prefix(&mut req, depth);
+        handler(req)
+    }))
+}
+
+fn strip_prefix(req: &mut Request, depth: usize) {
+    let uri = req.inner().uri().clone();
+    let rest: Vec<&str> = segments(uri.path()).skip(depth).collect();
+    let mut path_and_query = format!("/{}", rest.join("/"));
+    if let Some(query) = uri.query() {
+        path_and_query.push('?');
+        path_and_query.push_str(query);
+    }
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = PathAndQuery::try_from(path_and_query).ok();
+    let Ok(stripped) = Uri::from_parts(parts) else {
+        return;
+    };
+
+    let extensions = req.inner_mut().extensions_mut();
+    if extensions.get::<OriginalUri>().is_none() {
+        extensions.insert(OriginalUri(uri));
+    }
+    *req.inner_mut().uri_mut() = stripped;
+}
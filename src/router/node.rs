@@ -0,0 +1,328 @@
+use std::{collections::HashMap, sync::Arc};
+
+#[derive(Clone)]
+pub(crate) struct Node<T> {
+    value: Option<T>,
+    route: Option<Arc<str>>,
+    statics: HashMap<String, Node<T>>,
+    param: Option<(Arc<str>, Box<Node<T>>)>,
+    catch_all: Option<(Arc<str>, Box<Node<T>>)>,
+}
+
+pub(crate) struct Match<'a, T> {
+    pub value: &'a T,
+    pub route: &'a Arc<str>,
+}
+
+impl<T> Node<T> {
+    pub fn new() -> Self {
+        Self {
+            value: None,
+            route: None,
+            statics: HashMap::new(),
+            param: None,
+            catch_all: None,
+        }
+    }
+
+    pub fn entry(&mut self, path: &str) -> &mut Option<T> {
+        let route = format!("/{}", segments(path).collect::<Vec<_>>().join("/"));
+        let mut node = self;
+        let mut segments = segments(path).peekable();
+
+        while let Some(segment) = segments.next() {
+            node = if let Some(name) = segment.strip_prefix('*') {
+                assert!(
+                    segments.peek().is_none(),
+                    "catch-all segment `{}` must be the last segment in `{}`",
+                    segment,
+                    path
+                );
+                assert_valid_name(name, path);
+                child(&mut node.catch_all, name, '*', path)
+            } else if let Some(name) = segment.strip_prefix(':') {
+                assert_valid_name(name, path);
+                child(&mut node.param, name, ':', path)
+            } else {
+                node.statics.entry(segment.to_string()).or_default()
+            };
+        }
+
+        node.route.get_or_insert_with(|| route.into());
+        &mut node.value
+    }
+
+    pub fn at<'a>(&'a self, path: &str) -> Option<Match<'a, T>> {
+        let path = path.strip_prefix('/').unwrap_or(path);
+        let node = if path.is_empty() {
+            self.matched()
+        } else {
+            self.lookup(path)
+        }?;
+        Some(Match {
+            value: node.value.as_ref()?,
+            route: node.route.as_ref()?,
+        })
+    }
+
+    pub fn into_entries(self) -> Vec<(String, T)> {
+        let mut entries = Vec::new();
+        self.collect_entries(&mut Vec::new(), &mut entries);
+        entries
+    }
+
+    fn collect_entries(self, prefix: &mut Vec<String>, entries: &mut Vec<(String, T)>) {
+        if let Some(value) = self.value {
+            entries.push((format!("/{}", prefix.join("/")), value));
+        }
+
+        for (segment, child) in self.statics {
+            prefix.push(segment);
+            child.collect_entries(prefix, entries);
+            prefix.pop();
+        }
+
+        if let Some((name, child)) = self.param {
+            prefix.push(format!(":{}", name));
+            child.collect_entries(prefix, entries);
+            prefix.pop();
+        }
+
+        if let Some((name, child)) = self.catch_all {
+            prefix.push(format!("*{}", name));
+            child.collect_entries(prefix, entries);
+            prefix.pop();
+        }
+    }
+
+    fn lookup(&self, path: &str) -> Option<&Node<T>> {
+        let (segment, rest) = match path.split_once('/') {
+            Some((segment, rest)) => (segment, Some(rest)),
+            None => (path, None),
+        };
+
+        if let Some(child) = self.statics.get(segment) {
+            if let Some(node) = child.descend(rest) {
+                return Some(node);
+            }
+        }
+
+        if let Some((_, child)) = &self.param {
+            if !segment.is_empty() {
+                if let Some(node) = child.descend(rest) {
+                    return Some(node);
+                }
+            }
+        }
+
+        if let Some((_, child)) = &self.catch_all {
+            return child.matched();
+        }
+
+        None
+    }
+
+    fn descend(&self, rest: Option<&str>) -> Option<&Node<T>> {
+        match rest {
+            Some(rest) => self.lookup(rest),
+            None => self.matched(),
+        }
+    }
+
+    fn matched(&self) -> Option<&Node<T>> {
+        self.value.is_some().then_some(self)
+    }
+}
+
+impl<T> Default for Node<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn segments(path: &str) -> impl Iterator<Item = &str> {
+    let path = path.strip_prefix('/').unwrap_or(path);
+    (!path.is_empty())
+        .then(|| path.split('/'))
+        .into_iter()
+        .flatten()
+}
+
+fn child<'a, T>(
+    slot: &'a mut Option<(Arc<str>, Box<Node<T>>)>,
+    name: &str,
+    sigil: char,
+    path: &str,
+) -> &'a mut Node<T> {
+    let (existing, child) = slot.get_or_insert_with(|| (name.into(), Box::new(Node::new())));
+    assert_eq!(
+        &**existing, name,
+        "conflicting names `{}{}` and `{}{}` in `{}`",
+        sigil, existing, sigil, name, path
+    );
+    child
+}
+
+fn assert_valid_name(name: &str, path: &str) {
+    assert!(!name.is_empty(), "unnamed parameter in route `{}`", path);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http_request::PathParams;
+
+    fn router(paths: &[&str]) -> Node<usize> {
+        let mut node = Node::new();
+        for (index, path) in paths.iter().enumerate() {
+            *node.entry(path) = Some(index);
+        }
+        node
+    }
+
+    fn params(node: &Node<usize>, path: &str) -> Vec<(String, String)> {
+        let matched = node.at(path).unwrap();
+        PathParams::new(matched.route.clone(), path.parse().unwrap())
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn matches_root_and_static_routes() {
+        let node = router(&["/", "/about", "/about/team"]);
+
+        assert_eq!(node.at("/").map(|m| *m.value), Some(0));
+        assert_eq!(node.at("/about").map(|m| *m.value), Some(1));
+        assert_eq!(node.at("/about/team").map(|m| *m.value), Some(2));
+        assert!(node.at("/contact").is_none());
+        assert!(node.at("/about/team/x").is_none());
+    }
+
+    #[test]
+    fn static_takes_priority_over_param() {
+        let node = router(&["/users/:id", "/users/me"]);
+
+        assert_eq!(node.at("/users/me").map(|m| *m.value), Some(1));
+        assert!(params(&node, "/users/me").is_empty());
+        assert_eq!(node.at("/users/42").map(|m| *m.value), Some(0));
+        assert_eq!(params(&node, "/users/42"), pairs(&[("id", "42")]));
+    }
+
+    #[test]
+    fn param_takes_priority_over_catch_all() {
+        let node = router(&["/files/*path", "/files/:name"]);
+
+        assert_eq!(node.at("/files/a.txt").map(|m| *m.value), Some(1));
+        assert_eq!(params(&node, "/files/a.txt"), pairs(&[("name", "a.txt")]));
+        assert_eq!(node.at("/files/a/b.txt").map(|m| *m.value), Some(0));
+        assert_eq!(
+            params(&node, "/files/a/b.txt"),
+            pairs(&[("path", "a/b.txt")])
+        );
+    }
+
+    #[test]
+    fn backtracks_from_static_to_param() {
+        let node = router(&["/users/me/settings", "/users/:id/posts"]);
+
+        assert_eq!(node.at("/users/me/settings").map(|m| *m.value), Some(0));
+        assert_eq!(node.at("/users/me/posts").map(|m| *m.value), Some(1));
+        assert_eq!(params(&node, "/users/me/posts"), pairs(&[("id", "me")]));
+        assert!(node.at("/users/me/other").is_none());
+    }
+
+    #[test]
+    fn backtracks_from_param_to_catch_all() {
+        let node = router(&["/:a/x", "/*rest"]);
+
+        assert_eq!(node.at("/foo/x").map(|m| *m.value), Some(0));
+        assert_eq!(node.at("/foo/y").map(|m| *m.value), Some(1));
+        assert_eq!(params(&node, "/foo/y"), pairs(&[("rest", "foo/y")]));
+    }
+
+    #[test]
+    fn trailing_slash_is_a_distinct_route() {
+        let node = router(&["/assets", "/docs/"]);
+
+        assert_eq!(node.at("/assets").map(|m| *m.value), Some(0));
+        assert!(node.at("/assets/").is_none());
+        assert_eq!(node.at("/docs/").map(|m| *m.value), Some(1));
+        assert!(node.at("/docs").is_none());
+
+        let node = router(&["/assets", "/assets/"]);
+        assert_eq!(node.at("/assets").map(|m| *m.value), Some(0));
+        assert_eq!(node.at("/assets/").map(|m| *m.value), Some(1));
+    }
+
+    #[test]
+    fn param_does_not_match_empty_segment() {
+        let node = router(&["/users/:id"]);
+
+        assert!(node.at("/users/").is_none());
+        assert!(node.at("/users").is_none());
+    }
+
+    #[test]
+    fn catch_all_keeps_trailing_slash() {
+        let node = router(&["/static/*path"]);
+
+        assert_eq!(params(&node, "/static/css/"), pairs(&[("path", "css/")]));
+    }
+
+    #[test]
+    fn reports_matched_route() {
+        let node = router(&["/users/:id/posts/:post", "/files/*path"]);
+
+        assert_eq!(
+            node.at("/users/1/posts/2").map(|m| m.route.to_string()),
+            Some("/users/:id/posts/:post".to_string())
+        );
+        assert_eq!(
+            node.at("/files/a/b").map(|m| m.route.to_string()),
+            Some("/files/*path".to_string())
+        );
+    }
+
+    #[test]
+    fn into_entries_round_trips_routes() {
+        let node = router(&["/", "/users/:id", "/files/*path", "/docs/"]);
+        let mut entries = node.into_entries();
+        entries.sort();
+
+        assert_eq!(
+            entries,
+            vec![
+                ("/".to_string(), 0),
+                ("/docs/".to_string(), 3),
+                ("/files/*path".to_string(), 2),
+                ("/users/:id".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicting names `:id` and `:name`")]
+    fn panics_on_conflicting_param_names() {
+        router(&["/users/:id", "/users/:name/posts"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be the last segment")]
+    fn panics_on_catch_all_before_last_segment() {
+        router(&["/files/*path/edit"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "unnamed parameter")]
+    fn panics_on_unnamed_param() {
+        router(&["/users/:"]);
+    }
+}
@@ -1,8 +1,10 @@
-use crate::{Error, Request, Response, Result, Service};
+use crate::{
+    extractor::{ConnectInfo, ConnectionInfo},
+    Error, Request, Response, Result, Service,
+};
 use hyper::body::Incoming;
 use hyper::server::conn::http1;
 use hyper::service::service_fn as hyper_service_fn;
-use hyper::StatusCode;
 use hyper_util::rt::TokioIo;
 use rustls_pemfile::{certs, pkcs8_private_keys};
 use std::fs::File;
@@ -54,28 +56,32 @@ impl<S> Server<S> {
             let listener = TcpListener::bind(self.addr).await?;
 
             loop {
-                let (stream, _) = listener.accept().await?;
+                let (stream, peer_addr) = listener.accept().await?;
+                let Ok(local_addr) = stream.local_addr() else {
+                    continue;
+                };
                 let service = service.clone();
                 let acceptor = acceptor.clone();
 
                 tokio::spawn(async move {
                     match acceptor.accept(stream).await {
                         Ok(stream) => {
+                            let info = ConnectionInfo {
+                                peer_addr,
+                                local_addr,
+                                tls: true,
+                                alpn: stream.get_ref().1.alpn_protocol().map(<[u8]>::to_vec),
+                            };
                             let io = TokioIo::new(stream);
                             let hyper_service =
-                                hyper_service_fn(move |req: hyper::Request<Incoming>| {
+                                hyper_service_fn(move |mut req: hyper::Request<Incoming>| {
                                     let service = service.clone();
+                                    insert_connect_info(&mut req, &info);
                                     async move {
                                         let res = service.call(Request::from_hyper(req)).await;
-                                        match res {
-                                            Ok(res) => Ok::<_, hyper::Error>(res.into_hyper()),
-                                            Err(_) => {
-                                                let mut res =
-                                                    Response::new("Not Found").into_hyper();
-                                                *res.status_mut() = StatusCode::NOT_FOUND;
-                                                Ok(res)
-                                            }
-                                        }
+                                        Ok::<_, hyper::Error>(
+                                            res.unwrap_or_else(Response::from).into_hyper(),
+                                        )
                                     }
                                 });
 
@@ -95,25 +101,31 @@ impl<S> Server<S> {
         } else {
             let listener = TcpListener::bind(self.addr).await?;
             loop {
-                let (stream, _) = listener.accept().await?;
+                let (stream, peer_addr) = listener.accept().await?;
+                let Ok(local_addr) = stream.local_addr() else {
+                    continue;
+                };
+                let info = ConnectionInfo {
+                    peer_addr,
+                    local_addr,
+                    tls: false,
+                    alpn: None,
+                };
                 let io = TokioIo::new(stream);
                 let service = service.clone();
 
                 tokio::spawn(async move {
-                    let hyper_service = hyper_service_fn(move |req: hyper::Request<Incoming>| {
-                        let service = service.clone();
-                        async move {
-                            let res = service.call(Request::from_hyper(req)).await;
-                            match res {
-                                Ok(res) => Ok::<_, hyper::Error>(res.into_hyper()),
-                                Err(_) => {
-                                    let mut res = Response::new("Not Found").into_hyper();
-                                    *res.status_mut() = StatusCode::NOT_FOUND;
-                                    Ok(res)
-                                }
+                    let hyper_service =
+                        hyper_service_fn(move |mut req: hyper::Request<Incoming>| {
+                            let service = service.clone();
+                            insert_connect_info(&mut req, &info);
+                            async move {
+                                let res = service.call(Request::from_hyper(req)).await;
+                                Ok::<_, hyper::Error>(
+                                    res.unwrap_or_else(Response::from).into_hyper(),
+                                )
                             }
-                        }
-                    });
+                        });
 
                     if let Err(err) = http1::Builder::new()
                         .serve_connection(io, hyper_service)
@@ -127,16 +139,23 @@ impl<S> Server<S> {
     }
 }
 
+fn insert_connect_info(req: &mut hyper::Request<Incoming>, info: &ConnectionInfo) {
+    let extensions = req.extensions_mut();
+    extensions.insert(ConnectInfo(info.peer_addr));
+    extensions.insert(ConnectInfo(info.clone()));
+}
+
 impl TlsConfig {
     fn acceptor(&self) -> Result<Arc<TlsAcceptor>> {
         let certs = load_certs(&self.cert_path)?;
         let key = load_key(&self.key_path)?;
 
-        let config = ServerConfig::builder()
+        let mut config = ServerConfig::builder()
             .with_safe_defaults()
             .with_no_client_auth()
             .with_single_cert(certs, key)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
 
         Ok(Arc::new(TlsAcceptor::from(Arc::new(config))))
     }
@@ -152,8 +171,9 @@ fn load_key(path: &Path) -> std::io::Result<PrivateKey> {
     pkcs8_private_keys(&mut BufReader::new(File::open(path)?))
         .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid key"))
         .and_then(|mut keys| {
-            keys.pop()
-                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "no key found"))
+            keys.pop().ok_or_else(|| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, "no key found")
+            })
         })
         .map(PrivateKey)
-} 
\ No newline at end of file
+}
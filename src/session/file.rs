@@ -0,0 +1,191 @@
+use crate::{
+    session::store::{Record, SessionStore},
+    Error, Result,
+};
+use async_trait::async_trait;
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+use tokio::fs;
+
+#[derive(Clone, Debug)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    pub async fn purge_expired(&self) -> Result<()> {
+        let mut entries = match fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let expired = match fs::read(&path).await {
+                    Ok(bytes) => serde_json::from_slice::<Record>(&bytes)
+                        .map_or(true, |record| record.is_expired()),
+                    Err(_) => false,
+                };
+                if expired {
+                    let _ = fs::remove_file(&path).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn path(&self, id: &str) -> Option<PathBuf> {
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        valid.then(|| self.dir.join(format!("{}.json", id)))
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileStore {
+    async fn load(&self, id: &str) -> Result<Option<Record>> {
+        let Some(path) = self.path(id) else {
+            return Ok(None);
+        };
+        match fs::read(&path).await {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes).ok()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn save(&self, record: &Record) -> Result<()> {
+        let path = self
+            .path(&record.id)
+            .ok_or_else(|| Error::Internal(format!("Invalid session id `{}`", record.id)))?;
+        let bytes = serde_json::to_vec(record)
+            .map_err(|err| Error::Internal(format!("Failed to serialize session: {}", err)))?;
+
+        fs::create_dir_all(&self.dir).await?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, bytes).await?;
+        fs::rename(&tmp, &path).await?;
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        let Some(path) = self.path(id) else {
+            return Ok(());
+        };
+        match fs::remove_file(path).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        time::{Duration, SystemTime},
+    };
+
+    fn store(name: &str) -> FileStore {
+        let dir = std::env::temp_dir().join(format!(
+            "oxidized-{}-{}-{}",
+            name,
+            std::process::id(),
+            rand::random::<u32>()
+        ));
+        FileStore::new(dir)
+    }
+
+    fn record(id: &str, ttl: Duration) -> Record {
+        let now = SystemTime::now();
+        Record {
+            id: id.to_string(),
+            data: HashMap::from([("n".to_string(), 1.into())]),
+            created_at: now,
+            expires_at: now + ttl,
+        }
+    }
+
+    #[test]
+    fn path_rejects_ids_that_escape_the_directory() {
+        let store = FileStore::new("/sessions");
+
+        for id in [
+            "",
+            ".",
+            "..",
+            "../etc/passwd",
+            "a/b",
+            "a\\b",
+            "/abs",
+            "x.json",
+            "a b",
+        ] {
+            assert_eq!(store.path(id), None, "{:?}", id);
+        }
+        assert_eq!(
+            store.path("AbC-_09"),
+            Some(PathBuf::from("/sessions/AbC-_09.json"))
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_ids_never_touch_the_filesystem() {
+        let store = store("invalid");
+
+        assert!(store.load("../../etc/passwd").await.unwrap().is_none());
+        assert!(store.delete("../../etc/passwd").await.is_ok());
+        assert!(store
+            .save(&record("../escape", Duration::from_secs(60)))
+            .await
+            .is_err());
+        assert!(!store.dir.exists());
+    }
+
+    #[tokio::test]
+    async fn saves_loads_and_deletes_records() {
+        let store = store("roundtrip");
+
+        assert!(store.load("abc").await.unwrap().is_none());
+        store
+            .save(&record("abc", Duration::from_secs(60)))
+            .await
+            .unwrap();
+
+        let loaded = store.load("abc").await.unwrap().unwrap();
+        assert_eq!(loaded.id, "abc");
+        assert_eq!(loaded.data.get("n"), Some(&1.into()));
+
+        store.delete("abc").await.unwrap();
+        assert!(store.load("abc").await.unwrap().is_none());
+        let _ = fs::remove_dir_all(&store.dir).await;
+    }
+
+    #[tokio::test]
+    async fn purges_expired_records() {
+        let store = store("purge");
+        store
+            .save(&record("live", Duration::from_secs(60)))
+            .await
+            .unwrap();
+        store.save(&record("dead", Duration::ZERO)).await.unwrap();
+
+        store.purge_expired().await.unwrap();
+
+        assert!(store.load("live").await.unwrap().is_some());
+        assert!(store.load("dead").await.unwrap().is_none());
+        let _ = fs::remove_dir_all(&store.dir).await;
+    }
+}
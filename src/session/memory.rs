@@ -0,0 +1,47 @@
+use crate::{
+    session::store::{Record, SessionStore},
+    Result,
+};
+use async_trait::async_trait;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+    records: Arc<RwLock<HashMap<String, Record>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn purge_expired(&self) {
+        self.records
+            .write()
+            .unwrap()
+            .retain(|_, record| !record.is_expired());
+    }
+}
+
+#[async_trait]
+impl SessionStore for MemoryStore {
+    async fn load(&self, id: &str) -> Result<Option<Record>> {
+        Ok(self.records.read().unwrap().get(id).cloned())
+    }
+
+    async fn save(&self, record: &Record) -> Result<()> {
+        self.records
+            .write()
+            .unwrap()
+            .insert(record.id.clone(), record.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.records.write().unwrap().remove(id);
+        Ok(())
+    }
+}
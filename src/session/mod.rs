@@ -0,0 +1,320 @@
+use crate::{
+    extractor::{
+        cookie::{parse_cookies, Cookie, SameSite},
+        rejection::MissingSession,
+        FromRequestParts,
+    },
+    http_request::{Request, RequestParts},
+    middleware::Layer,
+    service::Service,
+    Error, Response, Result,
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use http::{header::SET_COOKIE, HeaderValue};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+
+mod file;
+mod memory;
+mod store;
+
+pub use self::file::FileStore;
+pub use self::memory::MemoryStore;
+pub use self::store::{Record, SessionStore};
+
+const DEFAULT_COOKIE_NAME: &str = "oxidized.sid";
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Clone, Debug)]
+struct Config {
+    cookie_name: String,
+    secure: bool,
+    max_age: Duration,
+    idle_timeout: Option<Duration>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SessionLayer<T> {
+    store: Arc<T>,
+    config: Config,
+}
+
+impl<T: SessionStore> SessionLayer<T> {
+    pub fn new(store: T) -> Self {
+        Self {
+            store: Arc::new(store),
+            config: Config {
+                cookie_name: DEFAULT_COOKIE_NAME.to_string(),
+                secure: true,
+                max_age: DEFAULT_MAX_AGE,
+                idle_timeout: None,
+            },
+        }
+    }
+
+    pub fn with_cookie_name<N: Into<String>>(mut self, name: N) -> Self {
+        self.config.cookie_name = name.into();
+        self
+    }
+
+    pub fn with_secure(mut self, secure: bool) -> Self {
+        self.config.secure = secure;
+        self
+    }
+
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.config.max_age = max_age;
+        self
+    }
+
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.config.idle_timeout = Some(idle_timeout);
+        self
+    }
+}
+
+impl<S, T> Layer<S> for SessionLayer<T> {
+    type Service = SessionService<S, T>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SessionService {
+            inner,
+            store: self.store.clone(),
+            config: Arc::new(self.config.clone()),
+        }
+    }
+}
+
+pub struct SessionService<S, T> {
+    inner: S,
+    store: Arc<T>,
+    config: Arc<Config>,
+}
+
+impl<S: Clone, T> Clone for SessionService<S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            store: self.store.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<S, T> Service<Request> for SessionService<S, T>
+where
+    S: Service<Request, Response = Response, Error = Error> + Clone + Send + Sync + 'static,
+    S::Future: Send,
+    T: SessionStore,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response>> + Send>>;
+
+    fn call(&self, mut req: Request) -> Self::Future {
+        let inner = self.inner.clone();
+        let store = self.store.clone();
+        let config = self.config.clone();
+        Box::pin(async move {
+            let id = parse_cookies(req.inner().headers())
+                .get(&config.cookie_name)
+                .map(|cookie| cookie.value().to_string());
+            let record = match &id {
+                Some(id) => store.load(id).await?,
+                None => None,
+            };
+            let record = match record {
+                Some(record) if record.is_expired() => {
+                    store.delete(&record.id).await?;
+                    None
+                }
+                record => record,
+            };
+            let stale = id.is_some() && record.is_none();
+
+            let session = Session::new(record);
+            req.extensions_mut().insert(session.clone());
+            let res = inner.call(req).await.unwrap_or_else(Response::from);
+
+            let cookie = persist(session.state(), &*store, &config).await?;
+            match cookie.or_else(|| stale.then(|| removal_cookie(&config))) {
+                Some(cookie) => Ok(set_cookie(res, cookie)),
+                None => Ok(res),
+            }
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Session {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct State {
+    id: Option<String>,
+    data: HashMap<String, Value>,
+    created_at: Option<SystemTime>,
+    changed: bool,
+    rotate: bool,
+    destroyed: bool,
+}
+
+impl Session {
+    fn new(record: Option<Record>) -> Self {
+        let state = match record {
+            Some(record) => State {
+                id: Some(record.id),
+                data: record.data,
+                created_at: Some(record.created_at),
+                ..State::default()
+            },
+            None => State::default(),
+        };
+        Self {
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+
+    pub fn id(&self) -> Option<String> {
+        self.state.lock().unwrap().id.clone()
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let value = self.state.lock().unwrap().data.get(key).cloned()?;
+        serde_json::from_value(value).ok()
+    }
+
+    pub fn insert<T: Serialize>(&self, key: &str, value: T) -> Result<()> {
+        let value = serde_json::to_value(value).map_err(|err| {
+            Error::Internal(format!("Failed to serialize session value: {}", err))
+        })?;
+        let mut state = self.state.lock().unwrap();
+        state.data.insert(key.to_string(), value);
+        state.changed = true;
+        Ok(())
+    }
+
+    pub fn remove<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let mut state = self.state.lock().unwrap();
+        let value = state.data.remove(key)?;
+        state.changed = true;
+        serde_json::from_value(value).ok()
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.data.clear();
+        state.changed = true;
+    }
+
+    pub fn rotate(&self) {
+        self.state.lock().unwrap().rotate = true;
+    }
+
+    pub fn destroy(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.data.clear();
+        state.destroyed = true;
+    }
+
+    fn state(&self) -> State {
+        self.state.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Session
+where
+    S: Send + Sync,
+{
+    type Rejection = MissingSession;
+
+    async fn from_request_parts(
+        parts: &mut RequestParts,
+        _state: &S,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        parts.extensions.get::<Session>().cloned().ok_or_else(|| {
+            MissingSession("Missing session, is `SessionLayer` applied?".to_string())
+        })
+    }
+}
+
+async fn persist<T: SessionStore>(
+    state: State,
+    store: &T,
+    config: &Config,
+) -> Result<Option<Cookie<'static>>> {
+    if state.destroyed {
+        return match state.id {
+            Some(id) => {
+                store.delete(&id).await?;
+                Ok(Some(removal_cookie(config)))
+            }
+            None => Ok(None),
+        };
+    }
+
+    let touch = state.id.is_some() && config.idle_timeout.is_some();
+    if !(state.changed || state.rotate || touch) || (state.id.is_none() && state.data.is_empty()) {
+        return Ok(None);
+    }
+
+    let now = SystemTime::now();
+    let (id, created_at) = match state.id {
+        Some(id) if !state.rotate => (id, state.created_at.unwrap_or(now)),
+        old => {
+            if let Some(old) = old {
+                store.delete(&old).await?;
+            }
+            (new_id(), now)
+        }
+    };
+    let mut expires_at = created_at + config.max_age;
+    if let Some(idle_timeout) = config.idle_timeout {
+        expires_at = expires_at.min(now + idle_timeout);
+    }
+
+    let record = Record {
+        id,
+        data: state.data,
+        created_at,
+        expires_at,
+    };
+    store.save(&record).await?;
+
+    let cookie = Cookie::build((config.cookie_name.clone(), record.id))
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .secure(config.secure)
+        .expires(::cookie::time::OffsetDateTime::from(expires_at))
+        .build();
+    Ok(Some(cookie))
+}
+
+fn removal_cookie(config: &Config) -> Cookie<'static> {
+    let mut cookie = Cookie::new(config.cookie_name.clone(), "");
+    cookie.set_path("/");
+    cookie.make_removal();
+    cookie
+}
+
+fn set_cookie(mut res: Response, cookie: Cookie<'_>) -> Response {
+    if let Ok(value) = HeaderValue::from_str(&cookie.to_string()) {
+        res.inner_mut().headers_mut().append(SET_COOKIE, value);
+    }
+    res
+}
+
+fn new_id() -> String {
+    URL_SAFE_NO_PAD.encode(rand::random::<[u8; 32]>())
+}
@@ -0,0 +1,28 @@
+use crate::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::HashMap, time::SystemTime};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Record {
+    pub id: String,
+    pub data: HashMap<String, Value>,
+    pub created_at: SystemTime,
+    pub expires_at: SystemTime,
+}
+
+impl Record {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= SystemTime::now()
+    }
+}
+
+#[async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    async fn load(&self, id: &str) -> Result<Option<Record>>;
+
+    async fn save(&self, record: &Record) -> Result<()>;
+
+    async fn delete(&self, id: &str) -> Result<()>;
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use bytes::Bytes;
+use common::{body, request, send, serve, status};
+use futures_util::StreamExt;
+use oxidized::{BodyStream, Response, Result, Router};
+use std::{sync::OnceLock, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::Notify,
+    time::timeout,
+};
+
+static FIRST_CHUNK: OnceLock<Notify> = OnceLock::new();
+
+fn payload() -> Vec<u8> {
+    (0..=255u8).cycle().take(100_000).collect()
+}
+
+async fn stream(mut body: BodyStream) -> Result<Response> {
+    let mut chunks = Vec::new();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        if chunks.is_empty() {
+            FIRST_CHUNK.get_or_init(Notify::new).notify_one();
+        }
+        chunks.push(String::from_utf8_lossy(&chunk).into_owned());
+    }
+    Ok(Response::new(format!("{:?}", chunks)))
+}
+
+async fn bytes(body: Bytes) -> Result<Response> {
+    Ok(Response::new(format!(
+        "{} {}",
+        body.len(),
+        body == payload()
+    )))
+}
+
+fn app() -> Router {
+    Router::new().post("/stream", stream).post("/bytes", bytes)
+}
+
+#[tokio::test]
+async fn body_stream_yields_chunks_as_they_arrive() {
+    let addr = serve(app()).await;
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(
+            b"POST /stream HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+              Transfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n",
+        )
+        .await
+        .unwrap();
+
+    // The second chunk is only sent once the handler has seen the first, so
+    // this hangs if the body were buffered before reaching the handler.
+    timeout(
+        Duration::from_secs(2),
+        FIRST_CHUNK.get_or_init(Notify::new).notified(),
+    )
+    .await
+    .expect("handler did not receive the first chunk on its own");
+
+    stream.write_all(b"6\r\n world\r\n0\r\n\r\n").await.unwrap();
+    let mut response = Vec::new();
+    timeout(Duration::from_secs(2), stream.read_to_end(&mut response))
+        .await
+        .unwrap()
+        .unwrap();
+    let response = String::from_utf8(response).unwrap();
+
+    assert_eq!(status(&response), 200);
+    assert_eq!(body(&response), r#"["hello", " world"]"#);
+}
+
+#[tokio::test]
+async fn bytes_returns_the_exact_payload() {
+    let addr = serve(app()).await;
+    let response = send(addr, &request("POST", "/bytes", &[], &payload())).await;
+    assert_eq!(status(&response), 200);
+    assert_eq!(body(&response), "100000 true");
+}
+
+#[tokio::test]
+async fn bytes_reassembles_a_chunked_payload() {
+    let addr = serve(app()).await;
+    let mut request = b"POST /bytes HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+                        Transfer-Encoding: chunked\r\n\r\n"
+        .to_vec();
+    for chunk in payload().chunks(7_000) {
+        request.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+        request.extend_from_slice(chunk);
+        request.extend_from_slice(b"\r\n");
+    }
+    request.extend_from_slice(b"0\r\n\r\n");
+
+    let response = send(addr, &request).await;
+    assert_eq!(status(&response), 200);
+    assert_eq!(body(&response), "100000 true");
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use bytes::Bytes;
+use common::{body, request, send, serve, status};
+use oxidized::{router::post, Json, Response, Result, Router};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+
+async fn bytes(body: Bytes) -> Result<Response> {
+    Ok(Response::new(body.len().to_string()))
+}
+
+async fn string(body: String) -> Result<Response> {
+    Ok(Response::new(body))
+}
+
+async fn json(Json(value): Json<Value>) -> Result<Response> {
+    Ok(Response::new(value.to_string()))
+}
+
+async fn post_to(router: Router, path: &str, payload: &[u8]) -> (u16, String) {
+    let addr = serve(router).await;
+    let headers = [("Content-Type", "application/json")];
+    let response = send(addr, &request("POST", path, &headers, payload)).await;
+    (status(&response), body(&response).to_string())
+}
+
+fn limited() -> Router {
+    Router::new()
+        .post("/bytes", bytes)
+        .post("/string", string)
+        .post("/json", json)
+        .body_limit(16)
+}
+
+#[tokio::test]
+async fn extractors_reject_bodies_over_the_limit() {
+    let payload = br#"{"name":"a value that is too long"}"#;
+    for path in ["/bytes", "/string", "/json"] {
+        let (status, body) = post_to(limited(), path, payload).await;
+        assert_eq!(status, 413, "{}", path);
+        assert_eq!(
+            body, "Request body exceeds the limit of 16 bytes",
+            "{}",
+            path
+        );
+    }
+}
+
+#[tokio::test]
+async fn extractors_accept_bodies_within_the_limit() {
+    assert_eq!(
+        post_to(limited(), "/bytes", b"0123456789abcdef").await,
+        (200, "16".to_string())
+    );
+    assert_eq!(
+        post_to(limited(), "/string", b"short").await,
+        (200, "short".to_string())
+    );
+    assert_eq!(
+        post_to(limited(), "/json", br#"{"a":1}"#).await,
+        (200, r#"{"a":1}"#.to_string())
+    );
+}
+
+#[tokio::test]
+async fn rejects_on_content_length_before_reading_the_body() {
+    let addr = serve(limited()).await;
+    let head = "POST /bytes HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+                Content-Length: 1000000\r\n\r\n";
+
+    let start = Instant::now();
+    let response = send(addr, head.as_bytes()).await;
+    assert_eq!(status(&response), 413);
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn rejects_chunked_bodies_that_outgrow_the_limit() {
+    let addr = serve(limited()).await;
+    let request = "POST /string HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+                   Transfer-Encoding: chunked\r\n\r\n\
+                   a\r\n0123456789\r\na\r\n0123456789\r\n0\r\n\r\n";
+
+    let response = send(addr, request.as_bytes()).await;
+    assert_eq!(status(&response), 413);
+}
+
+#[tokio::test]
+async fn method_router_limit_overrides_router_limit() {
+    let router = || {
+        Router::new()
+            .route("/raised", post(bytes).body_limit(64))
+            .route("/lowered", post(bytes).body_limit(4))
+            .post("/default", bytes)
+            .body_limit(16)
+    };
+    let payload = [b'x'; 32];
+
+    assert_eq!(post_to(router(), "/raised", &payload).await.0, 200);
+    assert_eq!(post_to(router(), "/default", &payload).await.0, 413);
+    assert_eq!(post_to(router(), "/default", b"12345678").await.0, 200);
+    assert_eq!(post_to(router(), "/lowered", b"12345678").await.0, 413);
+}
+
+#[tokio::test]
+async fn nested_router_limit_survives_nest() {
+    let router = || {
+        let api = Router::new().post("/bytes", bytes).body_limit(8);
+        Router::new()
+            .post("/bytes", bytes)
+            .nest("/api", api)
+            .body_limit(64)
+    };
+    let payload = [b'x'; 32];
+
+    assert_eq!(post_to(router(), "/bytes", &payload).await.0, 200);
+    assert_eq!(post_to(router(), "/api/bytes", &payload).await.0, 413);
+    assert_eq!(post_to(router(), "/api/bytes", b"1234").await.0, 200);
+}
+
+#[tokio::test]
+async fn merged_router_limit_survives_merge() {
+    let router = || {
+        let uploads = Router::new().post("/upload", bytes).body_limit(64);
+        Router::new()
+            .post("/bytes", bytes)
+            .merge(uploads)
+            .body_limit(8)
+    };
+    let payload = [b'x'; 32];
+
+    assert_eq!(post_to(router(), "/upload", &payload).await.0, 200);
+    assert_eq!(post_to(router(), "/bytes", &payload).await.0, 413);
+}
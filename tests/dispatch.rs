@@ -0,0 +1,97 @@
+use futures_util::FutureExt;
+use http::Uri;
+use oxidized::{http_request::Body, MatchedPath, Path, Request, Response, Result, Router, Service};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let value = f();
+    (value, ALLOCATIONS.with(Cell::get) - before)
+}
+
+async fn index() -> Result<Response> {
+    Ok(Response::new("index"))
+}
+
+async fn post(path: MatchedPath, Path((user, post)): Path<(String, u32)>) -> Result<Response> {
+    Ok(Response::new(format!(
+        "{} {} {}",
+        path.as_str(),
+        user,
+        post
+    )))
+}
+
+async fn file(Path(path): Path<String>) -> Result<Response> {
+    Ok(Response::new(path))
+}
+
+async fn fallback() -> Result<Response> {
+    Ok(Response::new("fallback"))
+}
+
+fn request(path: &'static str) -> Request {
+    let mut req = http::Request::new(Body::empty());
+    *req.uri_mut() = Uri::from_static(path);
+    Request::new(req)
+}
+
+async fn text(res: Result<Response>) -> String {
+    let body = res.unwrap().into_hyper().into_body();
+    let bytes = http_body_util::BodyExt::collect(body).await.unwrap();
+    String::from_utf8(bytes.to_bytes().to_vec()).unwrap()
+}
+
+#[test]
+fn dispatch_allocates_only_the_handler_future() {
+    let router = Router::new()
+        .get("/", index)
+        .get("/users/:user/posts/:post", post)
+        .get("/files/*path", file);
+    let with_fallback = router.clone().fallback(fallback);
+
+    for (router, path, expected) in [
+        (&router, "/", "index"),
+        (
+            &router,
+            "/users/alice/posts/7",
+            "/users/:user/posts/:post alice 7",
+        ),
+        (&router, "/files/css/site.css", "css/site.css"),
+        (&with_fallback, "/missing", "fallback"),
+    ] {
+        let req = request(path);
+        let (future, count) = allocations(|| router.call(req));
+        assert_eq!(count, 1, "{}", path);
+        let body = future.then(text).now_or_never().unwrap();
+        assert_eq!(body, expected);
+    }
+
+    let req = request("/missing");
+    let (future, count) = allocations(|| router.call(req));
+    assert_eq!(count, 1);
+    assert!(future.now_or_never().unwrap().is_err());
+}
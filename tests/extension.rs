@@ -0,0 +1,51 @@
+mod common;
+
+use common::{body, request, send, serve, status};
+use oxidized::{middleware::AddExtensionLayer, Extension, Response, Result, Router};
+
+#[derive(Clone)]
+struct RequestId(&'static str);
+
+#[derive(Clone)]
+struct Tenant(&'static str);
+
+async fn request_id(Extension(RequestId(id)): Extension<RequestId>) -> Result<Response> {
+    Ok(Response::new(format!("request {}", id)))
+}
+
+async fn tenant(Extension(Tenant(name)): Extension<Tenant>) -> Result<Response> {
+    Ok(Response::new(format!("tenant {}", name)))
+}
+
+async fn get(path: &str) -> (u16, String) {
+    let router = Router::new()
+        .get("/request-id", request_id)
+        .get("/tenant", tenant)
+        .nest("/api", Router::new().get("/request-id", request_id))
+        .layer(AddExtensionLayer::new(RequestId("abc-123")));
+    let addr = serve(router).await;
+    let response = send(addr, &request("GET", path, &[], b"")).await;
+    (status(&response), body(&response).to_string())
+}
+
+#[tokio::test]
+async fn layer_value_reaches_extension_extractor() {
+    assert_eq!(
+        get("/request-id").await,
+        (200, "request abc-123".to_string())
+    );
+    assert_eq!(
+        get("/api/request-id").await,
+        (200, "request abc-123".to_string())
+    );
+}
+
+#[tokio::test]
+async fn missing_extension_is_500() {
+    let (status, body) = get("/tenant").await;
+    assert_eq!(status, 500);
+    assert_eq!(
+        body,
+        "Missing request extension `extension::Tenant`, is it added with `AddExtensionLayer`?"
+    );
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::{body, header, request, send, serve, status};
+use oxidized::{Form, Response, Result, Router};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+struct Login {
+    username: String,
+    attempts: u64,
+    remember: Option<bool>,
+    #[serde(default)]
+    scope: Vec<String>,
+}
+
+async fn login(Form(login): Form<Login>) -> Result<Response> {
+    Ok(Response::new(format!(
+        "{} {} {:?} {:?}",
+        login.username, login.attempts, login.remember, login.scope
+    )))
+}
+
+async fn echo(Form(login): Form<Login>) -> Result<Response> {
+    Ok(Form(login).into())
+}
+
+fn app() -> Router {
+    Router::new()
+        .post("/login", login)
+        .get("/login", login)
+        .post("/echo", echo)
+}
+
+async fn call(method: &str, path: &str, headers: &[(&str, &str)], payload: &[u8]) -> String {
+    let addr = serve(app()).await;
+    send(addr, &request(method, path, headers, payload)).await
+}
+
+const FORM: (&str, &str) = ("Content-Type", "application/x-www-form-urlencoded");
+
+#[tokio::test]
+async fn decodes_urlencoded_bodies() {
+    let response = call(
+        "POST",
+        "/login",
+        &[FORM],
+        b"username=j%C3%BCrgen+k&attempts=3&scope=read&scope=write",
+    )
+    .await;
+    assert_eq!(status(&response), 200);
+    assert_eq!(body(&response), r#"jürgen k 3 None ["read", "write"]"#);
+}
+
+#[tokio::test]
+async fn get_reads_the_query_string() {
+    let response = call(
+        "GET",
+        "/login?username=alice&attempts=1&remember=true",
+        &[],
+        b"username=ignored&attempts=9",
+    )
+    .await;
+    assert_eq!(status(&response), 200);
+    assert_eq!(body(&response), "alice 1 Some(true) []");
+}
+
+#[tokio::test]
+async fn wrong_content_type_is_415() {
+    let response = call(
+        "POST",
+        "/login",
+        &[("Content-Type", "application/json")],
+        br#"{"username":"alice","attempts":1}"#,
+    )
+    .await;
+    assert_eq!(status(&response), 415);
+    assert_eq!(
+        body(&response),
+        "Expected request with `Content-Type: application/x-www-form-urlencoded`"
+    );
+
+    let response = call("POST", "/login", &[], b"username=alice&attempts=1").await;
+    assert_eq!(status(&response), 415);
+}
+
+#[tokio::test]
+async fn bad_data_is_400() {
+    let response = call("POST", "/login", &[FORM], b"username=alice&attempts=many").await;
+    assert_eq!(status(&response), 400);
+    assert!(
+        body(&response).starts_with("Failed to deserialize form:"),
+        "{}",
+        body(&response)
+    );
+
+    let response = call("POST", "/login", &[FORM], b"username=alice").await;
+    assert_eq!(status(&response), 400);
+}
+
+#[tokio::test]
+async fn response_round_trips() {
+    let payload = format!(
+        "username=alice+%26+bob&attempts={}&remember=false&scope=read&scope=write",
+        u64::MAX
+    );
+    let response = call("POST", "/echo", &[FORM], payload.as_bytes()).await;
+    assert_eq!(status(&response), 200);
+    assert_eq!(
+        header(&response, "content-type"),
+        Some("application/x-www-form-urlencoded")
+    );
+    assert_eq!(body(&response), payload);
+}
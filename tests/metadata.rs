@@ -0,0 +1,91 @@
+mod common;
+
+use common::{body, request, send, serve, status};
+use http::{HeaderMap, Method, Uri, Version};
+use oxidized::{MatchedPath, OriginalUri, Response, Result, Router};
+
+async fn describe(
+    method: Method,
+    version: Version,
+    headers: HeaderMap,
+    path: MatchedPath,
+    uri: Uri,
+    OriginalUri(original): OriginalUri,
+) -> Result<Response> {
+    Ok(Response::new(format!(
+        "{} {:?} {} {} {} {}",
+        method,
+        version,
+        headers["x-request-id"].to_str().unwrap(),
+        path.as_str(),
+        uri,
+        original
+    )))
+}
+
+async fn fallback(path: Option<MatchedPath>, uri: Uri) -> Result<Response> {
+    Ok(Response::new(format!(
+        "fallback {:?} {}",
+        path.as_ref().map(MatchedPath::as_str),
+        uri
+    )))
+}
+
+async fn required(path: MatchedPath) -> Result<Response> {
+    Ok(Response::new(path.as_str().to_string()))
+}
+
+fn app() -> Router {
+    let items = Router::new().get("/:id", describe).fallback(fallback);
+    let api = Router::new().nest("/items", items);
+    Router::new()
+        .get("/health", describe)
+        .nest("/api", api)
+        .fallback(fallback)
+}
+
+async fn get(router: Router, path: &str) -> (u16, String) {
+    let addr = serve(router).await;
+    let response = send(addr, &request("GET", path, &[("X-Request-Id", "abc")], b"")).await;
+    (status(&response), body(&response).to_string())
+}
+
+#[tokio::test]
+async fn extracts_request_metadata() {
+    assert_eq!(
+        get(app(), "/health?verbose=1").await,
+        (
+            200,
+            "GET HTTP/1.1 abc /health /health?verbose=1 /health?verbose=1".to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn matched_path_includes_nest_prefixes_and_uri_is_stripped() {
+    assert_eq!(
+        get(app(), "/api/items/42?full=true").await,
+        (
+            200,
+            "GET HTTP/1.1 abc /api/items/:id /42?full=true /api/items/42?full=true".to_string()
+        )
+    );
+}
+
+#[tokio::test]
+async fn matched_path_is_none_in_a_fallback() {
+    assert_eq!(
+        get(app(), "/missing").await,
+        (200, "fallback None /missing".to_string())
+    );
+    assert_eq!(
+        get(app(), "/api/items/42/extra").await,
+        (200, "fallback None /42/extra".to_string())
+    );
+}
+
+#[tokio::test]
+async fn required_matched_path_in_a_fallback_is_500() {
+    let (status, _) = get(Router::new().fallback(required), "/missing").await;
+    assert_eq!(status, 500);
+}
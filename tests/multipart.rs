@@ -0,0 +1,170 @@
+mod common;
+
+use common::{body, request, send, serve, status};
+use oxidized::{Multipart, Response, Result, Router};
+
+const CONTENT_TYPE: (&str, &str) = ("Content-Type", "multipart/form-data; boundary=X");
+
+async fn count(multipart: Multipart) -> Result<Response> {
+    read(multipart).await
+}
+
+async fn field_limited(multipart: Multipart) -> Result<Response> {
+    read(multipart.with_field_limit(16)).await
+}
+
+async fn total_limited(multipart: Multipart) -> Result<Response> {
+    read(multipart.with_total_limit(256)).await
+}
+
+async fn raised_limit(multipart: Multipart) -> Result<Response> {
+    read(multipart.with_total_limit(1024 * 1024)).await
+}
+
+async fn read(mut multipart: Multipart) -> Result<Response> {
+    let mut total = 0;
+    while let Some(field) = multipart.next_field().await? {
+        total += field.bytes().await?.len();
+    }
+    Ok(Response::new(total.to_string()))
+}
+
+fn part(headers: &str, data: &str) -> String {
+    format!(
+        "--X\r\nContent-Disposition: form-data; name=\"f\"{}\r\n\r\n{}\r\n",
+        headers, data
+    )
+}
+
+async fn app() -> std::net::SocketAddr {
+    serve(
+        Router::new()
+            .post("/", count)
+            .post("/field", field_limited)
+            .post("/total", total_limited),
+    )
+    .await
+}
+
+async fn limited_app() -> std::net::SocketAddr {
+    serve(
+        Router::new()
+            .post("/", count)
+            .post("/raised", raised_limit)
+            .body_limit(64),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn reads_fields_within_limits() {
+    let addr = app().await;
+    let payload = format!("{}{}--X--\r\n", part("", "hello"), part("", "world"));
+
+    let res = send(
+        addr,
+        &request("POST", "/total", &[CONTENT_TYPE], payload.as_bytes()),
+    )
+    .await;
+    assert_eq!(status(&res), 200);
+    assert_eq!(body(&res), "10");
+}
+
+#[tokio::test]
+async fn field_over_limit_is_rejected() {
+    let addr = app().await;
+    let payload = format!("{}--X--\r\n", part("", &"a".repeat(17)));
+
+    let res = send(
+        addr,
+        &request("POST", "/field", &[CONTENT_TYPE], payload.as_bytes()),
+    )
+    .await;
+    assert_eq!(status(&res), 413);
+}
+
+#[tokio::test]
+async fn total_limit_counts_preamble() {
+    let addr = app().await;
+    let payload = format!("{}\r\n{}--X--\r\n", "p".repeat(1024), part("", "hi"));
+
+    let res = send(
+        addr,
+        &request("POST", "/total", &[CONTENT_TYPE], payload.as_bytes()),
+    )
+    .await;
+    assert_eq!(status(&res), 413);
+}
+
+#[tokio::test]
+async fn total_limit_counts_part_headers() {
+    let addr = app().await;
+    let header = format!("\r\nX-Padding: {}", "h".repeat(1024));
+    let payload = format!("{}--X--\r\n", part(&header, "hi"));
+
+    let res = send(
+        addr,
+        &request("POST", "/total", &[CONTENT_TYPE], payload.as_bytes()),
+    )
+    .await;
+    assert_eq!(status(&res), 413);
+}
+
+#[tokio::test]
+async fn field_limit_counts_field_data_only() {
+    let addr = app().await;
+    let header = format!("\r\nX-Padding: {}", "h".repeat(64));
+    let payload = format!("{}--X--\r\n", part(&header, "short"));
+
+    let res = send(
+        addr,
+        &request("POST", "/field", &[CONTENT_TYPE], payload.as_bytes()),
+    )
+    .await;
+    assert_eq!(status(&res), 200);
+    assert_eq!(body(&res), "5");
+}
+
+#[tokio::test]
+async fn content_length_over_body_limit_is_rejected_early() {
+    let addr = limited_app().await;
+    let header = format!("\r\nX-Padding: {}", "h".repeat(1024));
+    let payload = format!("{}--X--\r\n", part(&header, "hi"));
+
+    let res = send(
+        addr,
+        &request("POST", "/", &[CONTENT_TYPE], payload.as_bytes()),
+    )
+    .await;
+    assert_eq!(status(&res), 413);
+}
+
+fn chunked(path: &str, payload: &str) -> Vec<u8> {
+    format!(
+        "POST {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+         Content-Type: {}\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+        path,
+        CONTENT_TYPE.1,
+        payload.len(),
+        payload
+    )
+    .into_bytes()
+}
+
+#[tokio::test]
+async fn chunked_body_over_body_limit_is_rejected() {
+    let addr = limited_app().await;
+    let payload = format!("{}\r\n{}--X--\r\n", "p".repeat(1024), part("", "hi"));
+
+    let res = send(addr, &chunked("/", &payload)).await;
+    assert_eq!(status(&res), 413);
+}
+
+#[tokio::test]
+async fn total_limit_cannot_exceed_body_limit() {
+    let addr = limited_app().await;
+    let payload = format!("{}--X--\r\n", part("", &"a".repeat(128)));
+
+    let res = send(addr, &chunked("/raised", &payload)).await;
+    assert_eq!(status(&res), 413);
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use common::{body, request, send, serve, status};
+use oxidized::{
+    extractor::rejection::{JsonRejection, TypedHeaderRejection},
+    headers::{Accept, UserAgent},
+    Json, Response, Result, Router, TypedHeader,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct User {
+    name: String,
+}
+
+async fn optional(
+    agent: Option<TypedHeader<UserAgent>>,
+    user: Option<Json<User>>,
+) -> Result<Response> {
+    let agent = agent.map(|TypedHeader(agent)| agent.to_string());
+    let user = user.map(|Json(user)| user.name);
+    Ok(Response::new(format!("{:?} {:?}", agent, user)))
+}
+
+async fn fallible(
+    accept: std::result::Result<TypedHeader<Accept>, TypedHeaderRejection>,
+    user: std::result::Result<Json<User>, JsonRejection>,
+) -> Result<Response> {
+    let accept = match accept {
+        Ok(_) => "accept ok".to_string(),
+        Err(TypedHeaderRejection::MissingHeader(err)) => format!("missing: {}", err),
+        Err(TypedHeaderRejection::InvalidHeader(err)) => format!("invalid: {}", err),
+    };
+    let user = match user {
+        Ok(Json(user)) => format!("user {}", user.name),
+        Err(JsonRejection::MissingJsonContentType(_)) => "no json content type".to_string(),
+        Err(JsonRejection::JsonSyntaxError(_)) => "syntax error".to_string(),
+        Err(JsonRejection::JsonDataError(_)) => "data error".to_string(),
+        Err(JsonRejection::BytesRejection(err)) => format!("body: {}", err),
+    };
+    Ok(Response::new(format!("{}; {}", accept, user)))
+}
+
+fn app() -> Router {
+    Router::new()
+        .post("/optional", optional)
+        .post("/fallible", fallible)
+}
+
+async fn post(path: &str, headers: &[(&str, &str)], payload: &[u8]) -> (u16, String) {
+    let addr = serve(app()).await;
+    let response = send(addr, &request("POST", path, headers, payload)).await;
+    (status(&response), body(&response).to_string())
+}
+
+const JSON: (&str, &str) = ("Content-Type", "application/json");
+
+#[tokio::test]
+async fn option_wrappers_receive_some_when_extraction_succeeds() {
+    let response = post(
+        "/optional",
+        &[("User-Agent", "curl/8.0"), JSON],
+        br#"{"name":"alice"}"#,
+    )
+    .await;
+    assert_eq!(
+        response,
+        (200, r#"Some("curl/8.0") Some("alice")"#.to_string())
+    );
+}
+
+#[tokio::test]
+async fn option_wrappers_receive_none_instead_of_rejecting() {
+    assert_eq!(
+        post("/optional", &[], b"").await,
+        (200, "None None".to_string())
+    );
+    assert_eq!(
+        post("/optional", &[JSON], b"{not json").await,
+        (200, "None None".to_string())
+    );
+    assert_eq!(
+        post(
+            "/optional",
+            &[("User-Agent", "curl/8.0")],
+            br#"{"name":"alice"}"#
+        )
+        .await,
+        (200, r#"Some("curl/8.0") None"#.to_string())
+    );
+}
+
+#[tokio::test]
+async fn result_wrappers_receive_the_value() {
+    assert_eq!(
+        post(
+            "/fallible",
+            &[("Accept", "application/json"), JSON],
+            br#"{"name":"alice"}"#
+        )
+        .await,
+        (200, "accept ok; user alice".to_string())
+    );
+}
+
+#[tokio::test]
+async fn result_wrappers_receive_the_rejection_instead_of_rejecting() {
+    assert_eq!(
+        post("/fallible", &[], br#"{"name":"alice"}"#).await,
+        (
+            200,
+            "missing: Missing request header `accept`; no json content type".to_string()
+        )
+    );
+    assert_eq!(
+        post(
+            "/fallible",
+            &[("Accept", "text/html;q=2"), JSON],
+            b"{not json"
+        )
+        .await,
+        (
+            200,
+            "invalid: Invalid request header `accept`: invalid HTTP header; syntax error"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        post("/fallible", &[("Accept", "*/*"), JSON], br#"{"name":1}"#).await,
+        (200, "accept ok; data error".to_string())
+    );
+}
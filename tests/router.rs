@@ -0,0 +1,265 @@
+mod common;
+
+use common::{body, header, request, send, serve, status};
+use http::{StatusCode, Uri};
+use oxidized::{router::MethodRouter, ws::WebSocket, OriginalUri, Path, Response, Result, Router};
+use std::net::SocketAddr;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+async fn post(Path((user, post)): Path<(String, u32)>) -> Result<Response> {
+    Ok(Response::new(format!("{} {}", user, post)))
+}
+
+async fn versioned(Path((version, user, post)): Path<(String, String, u32)>) -> Result<Response> {
+    Ok(Response::new(format!("{} {} {}", version, user, post)))
+}
+
+async fn file(Path(path): Path<String>) -> Result<Response> {
+    Ok(Response::new(path))
+}
+
+fn app() -> Router {
+    let api = Router::new().get("/users/:user/posts/:post", versioned);
+    Router::new()
+        .get("/users/:user/posts/:post", post)
+        .get("/files/*path", file)
+        .nest("/api/:version", api)
+}
+
+async fn list() -> Result<Response> {
+    Ok(Response::new("items"))
+}
+
+async fn create() -> Result<Response> {
+    Ok(Response::new("created"))
+}
+
+fn items() -> Router {
+    Router::new().route("/items", MethodRouter::new().get(list).post(create))
+}
+
+async fn call(router: Router, method: &str, path: &str) -> String {
+    let addr = serve(router).await;
+    send(addr, &request(method, path, &[], b"")).await
+}
+
+async fn get(path: &str) -> (u16, String) {
+    let response = call(app(), "GET", path).await;
+    (status(&response), body(&response).to_string())
+}
+
+#[tokio::test]
+async fn extracts_path_params() {
+    assert_eq!(
+        get("/users/alice/posts/7").await,
+        (200, "alice 7".to_string())
+    );
+}
+
+#[tokio::test]
+async fn decodes_path_params() {
+    assert_eq!(
+        get("/users/j%C3%BCrgen/posts/7?page=2").await,
+        (200, "jürgen 7".to_string())
+    );
+}
+
+#[tokio::test]
+async fn extracts_catch_all_remainder() {
+    assert_eq!(
+        get("/files/css/site/main.css").await,
+        (200, "css/site/main.css".to_string())
+    );
+}
+
+#[tokio::test]
+async fn nested_routes_see_params_of_the_full_path() {
+    assert_eq!(
+        get("/api/v2/users/alice/posts/7").await,
+        (200, "v2 alice 7".to_string())
+    );
+}
+
+#[tokio::test]
+async fn unsupported_method_is_405_with_allow_header() {
+    let response = call(items(), "DELETE", "/items").await;
+    assert_eq!(status(&response), 405);
+    assert_eq!(header(&response, "allow"), Some("GET, POST, HEAD, OPTIONS"));
+}
+
+#[tokio::test]
+async fn head_is_served_by_the_get_handler() {
+    let response = call(items(), "HEAD", "/items").await;
+    assert_eq!(status(&response), 200);
+    assert_eq!(header(&response, "content-length"), Some("5"));
+    assert_eq!(body(&response), "");
+}
+
+#[tokio::test]
+async fn options_is_answered_with_204_and_allow_header() {
+    let response = call(items(), "OPTIONS", "/items").await;
+    assert_eq!(status(&response), 204);
+    assert_eq!(header(&response, "allow"), Some("GET, POST, HEAD, OPTIONS"));
+    assert_eq!(body(&response), "");
+}
+
+#[tokio::test]
+async fn explicit_head_and_options_handlers_are_not_listed_twice() {
+    let router = Router::new().route(
+        "/items",
+        MethodRouter::new().get(list).head(list).options(list),
+    );
+    let response = call(router, "PUT", "/items").await;
+    assert_eq!(status(&response), 405);
+    assert_eq!(header(&response, "allow"), Some("GET, HEAD, OPTIONS"));
+}
+
+async fn socket(_: WebSocket) {}
+
+#[tokio::test]
+async fn merge_unions_methods_on_the_same_path() {
+    let router = Router::new()
+        .get("/items", list)
+        .merge(Router::new().post("/items", create));
+    assert_eq!(body(&call(router.clone(), "GET", "/items").await), "items");
+    assert_eq!(body(&call(router, "POST", "/items").await), "created");
+}
+
+#[test]
+#[should_panic(expected = "overlapping route: GET `/items` is already registered")]
+fn merge_panics_on_duplicate_routes() {
+    let _: Router = Router::new()
+        .get("/items", list)
+        .merge(Router::new().get("/items", create));
+}
+
+#[test]
+#[should_panic(expected = "overlapping route: websocket `/ws` is already registered")]
+fn merge_panics_on_duplicate_ws_routes() {
+    let _: Router = Router::new()
+        .ws("/ws", socket)
+        .merge(Router::new().ws("/ws", socket));
+}
+
+#[test]
+#[should_panic(expected = "overlapping fallback: `/` already has a fallback handler")]
+fn merge_panics_on_duplicate_fallbacks() {
+    let _: Router = Router::new()
+        .fallback(list)
+        .merge(Router::new().fallback(create));
+}
+
+async fn root_fallback(uri: Uri) -> Result<Response> {
+    Ok(Response::new(format!("root {}", uri)).status(StatusCode::NOT_FOUND))
+}
+
+async fn api_fallback(uri: Uri) -> Result<Response> {
+    Ok(Response::new(format!("api {}", uri)).status(StatusCode::NOT_FOUND))
+}
+
+fn with_fallbacks() -> Router {
+    let api = Router::new().get("/items", list).fallback(api_fallback);
+    items().nest("/api", api).fallback(root_fallback)
+}
+
+#[tokio::test]
+async fn unmatched_path_hits_the_fallback() {
+    let response = call(with_fallbacks(), "GET", "/missing?page=2").await;
+    assert_eq!(status(&response), 404);
+    assert_eq!(body(&response), "root /missing?page=2");
+}
+
+#[tokio::test]
+async fn nested_fallback_applies_only_under_its_prefix() {
+    for (path, expected) in [
+        ("/api", "api /"),
+        ("/api/", "api /"),
+        ("/api/missing", "api /missing"),
+        ("/api/items/1", "api /items/1"),
+        ("/apix", "root /apix"),
+        ("/other/api", "root /other/api"),
+    ] {
+        let response = call(with_fallbacks(), "GET", path).await;
+        assert_eq!(body(&response), expected, "{}", path);
+    }
+    let response = call(with_fallbacks(), "GET", "/api/items").await;
+    assert_eq!(body(&response), "items");
+}
+
+#[tokio::test]
+async fn method_not_allowed_wins_over_the_fallback() {
+    let response = call(with_fallbacks(), "DELETE", "/items").await;
+    assert_eq!(status(&response), 405);
+    assert_eq!(header(&response, "allow"), Some("GET, POST, HEAD, OPTIONS"));
+
+    let response = call(with_fallbacks(), "POST", "/api/items").await;
+    assert_eq!(status(&response), 405);
+}
+
+#[tokio::test]
+async fn unmatched_path_without_fallback_is_404() {
+    let response = call(items(), "GET", "/missing").await;
+    assert_eq!(status(&response), 404);
+}
+
+async fn uris(uri: Uri, OriginalUri(original): OriginalUri) -> Result<Response> {
+    Ok(Response::new(format!("{} {}", uri, original)))
+}
+
+#[tokio::test]
+async fn nested_handler_sees_the_stripped_uri_with_its_query() {
+    let router = Router::new().nest("/api", Router::new().get("/items", uris));
+    let response = call(router, "GET", "/api/items?page=2&sort=name").await;
+    assert_eq!(
+        body(&response),
+        "/items?page=2&sort=name /api/items?page=2&sort=name"
+    );
+}
+
+#[tokio::test]
+async fn original_uri_survives_two_levels_of_nesting() {
+    let v1 = Router::new().get("/items/:id", uris);
+    let api = Router::new().nest("/v1", v1);
+    let router = Router::new().nest("/api", api);
+
+    let response = call(router, "GET", "/api/v1/items/7?page=2").await;
+    assert_eq!(body(&response), "/items/7?page=2 /api/v1/items/7?page=2");
+}
+
+async fn upgrade(addr: SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\
+         Sec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n",
+        path
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut head = Vec::new();
+    while !head.ends_with(b"\r\n\r\n") {
+        let mut byte = [0];
+        if stream.read(&mut byte).await.unwrap() == 0 {
+            break;
+        }
+        head.push(byte[0]);
+    }
+    String::from_utf8(head).unwrap()
+}
+
+#[tokio::test]
+async fn nested_ws_routes_are_mounted_under_the_prefix() {
+    let addr = serve(Router::new().nest("/api", Router::new().ws("/ws", socket))).await;
+
+    let response = upgrade(addr, "/api/ws").await;
+    assert_eq!(status(&response), 101);
+    assert_eq!(
+        header(&response, "sec-websocket-accept"),
+        Some("s3pPLMBiTxaQ9kYGzzhZRbK+xOo=")
+    );
+
+    let response = upgrade(addr, "/ws").await;
+    assert_eq!(status(&response), 404);
+}
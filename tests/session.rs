@@ -0,0 +1,205 @@
+mod common;
+
+use common::{body, header_values, request, send, serve, status};
+use oxidized::{
+    session::{MemoryStore, SessionStore},
+    Error, Response, Result, Router, Session, SessionLayer,
+};
+use std::{net::SocketAddr, time::Duration};
+
+async fn count(session: Session) -> Result<Response> {
+    let n = session.get::<u32>("n").unwrap_or(0) + 1;
+    session.insert("n", n)?;
+    Ok(Response::new(n.to_string()))
+}
+
+async fn show(session: Session) -> Result<Response> {
+    let user = session.get::<String>("user").unwrap_or_default();
+    let n = session.get::<u32>("n").unwrap_or(0);
+    Ok(Response::new(format!("{} {}", user, n)))
+}
+
+async fn login(session: Session) -> Result<Response> {
+    session.insert("user", "alice")?;
+    session.rotate();
+    Ok(Response::new("logged in"))
+}
+
+async fn logout(session: Session) -> Result<Response> {
+    session.destroy();
+    Ok(Response::new("logged out"))
+}
+
+async fn failing_logout(session: Session) -> Result<Response> {
+    session.destroy();
+    Err(Error::BadRequest("audit log unavailable".to_string()))
+}
+
+async fn failing_login(session: Session) -> Result<Response> {
+    session.rotate();
+    Err(Error::BadRequest("welcome email failed".to_string()))
+}
+
+fn routes() -> Router {
+    Router::new()
+        .get("/count", count)
+        .get("/show", show)
+        .post("/login", login)
+        .post("/logout", logout)
+        .post("/failing-logout", failing_logout)
+        .post("/failing-login", failing_login)
+}
+
+async fn app(layer: SessionLayer<MemoryStore>) -> SocketAddr {
+    serve(routes().layer(layer)).await
+}
+
+fn session_cookie(response: &str) -> Option<String> {
+    header_values(response, "set-cookie")
+        .into_iter()
+        .find_map(|cookie| cookie.strip_prefix("oxidized.sid="))
+        .map(|cookie| cookie.split(';').next().unwrap_or_default().to_string())
+}
+
+async fn call(addr: SocketAddr, method: &str, path: &str, id: Option<&str>) -> String {
+    let cookie = id.map(|id| format!("oxidized.sid={}", id));
+    let headers: Vec<(&str, &str)> = cookie
+        .as_deref()
+        .map(|cookie| vec![("Cookie", cookie)])
+        .unwrap_or_default();
+    send(addr, &request(method, path, &headers, b"")).await
+}
+
+#[tokio::test]
+async fn persists_changes_and_reloads_them() {
+    let store = MemoryStore::new();
+    let addr = app(SessionLayer::new(store.clone())).await;
+
+    let res = call(addr, "GET", "/count", None).await;
+    assert_eq!(body(&res), "1");
+    let id = session_cookie(&res).unwrap();
+    let record = store.load(&id).await.unwrap().unwrap();
+    assert_eq!(record.data.get("n"), Some(&1.into()));
+
+    let res = call(addr, "GET", "/count", Some(&id)).await;
+    assert_eq!(body(&res), "2");
+    assert_eq!(session_cookie(&res), Some(id));
+}
+
+#[tokio::test]
+async fn untouched_session_sets_no_cookie() {
+    let addr = app(SessionLayer::new(MemoryStore::new())).await;
+
+    let res = call(addr, "GET", "/show", None).await;
+    assert_eq!(status(&res), 200);
+    assert!(header_values(&res, "set-cookie").is_empty());
+}
+
+#[tokio::test]
+async fn rotate_issues_new_id_and_deletes_old_one() {
+    let store = MemoryStore::new();
+    let addr = app(SessionLayer::new(store.clone())).await;
+    let old = session_cookie(&call(addr, "GET", "/count", None).await).unwrap();
+
+    let res = call(addr, "POST", "/login", Some(&old)).await;
+    let new = session_cookie(&res).unwrap();
+
+    assert_ne!(old, new);
+    assert!(store.load(&old).await.unwrap().is_none());
+    assert_eq!(
+        body(&call(addr, "GET", "/show", Some(&new)).await),
+        "alice 1"
+    );
+}
+
+#[tokio::test]
+async fn destroy_deletes_record_and_clears_cookie() {
+    let store = MemoryStore::new();
+    let addr = app(SessionLayer::new(store.clone())).await;
+    let id = session_cookie(&call(addr, "GET", "/count", None).await).unwrap();
+
+    let res = call(addr, "POST", "/logout", Some(&id)).await;
+
+    assert_eq!(session_cookie(&res).as_deref(), Some(""));
+    assert!(res.contains("Max-Age=0"));
+    assert!(store.load(&id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn destroy_is_applied_when_handler_fails() {
+    let store = MemoryStore::new();
+    let addr = app(SessionLayer::new(store.clone())).await;
+    let id = session_cookie(&call(addr, "GET", "/count", None).await).unwrap();
+
+    let res = call(addr, "POST", "/failing-logout", Some(&id)).await;
+
+    assert_eq!(status(&res), 400);
+    assert_eq!(session_cookie(&res).as_deref(), Some(""));
+    assert!(store.load(&id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn rotate_is_applied_when_handler_fails() {
+    let store = MemoryStore::new();
+    let addr = app(SessionLayer::new(store.clone())).await;
+    let old = session_cookie(&call(addr, "GET", "/count", None).await).unwrap();
+
+    let res = call(addr, "POST", "/failing-login", Some(&old)).await;
+
+    assert_eq!(status(&res), 400);
+    let new = session_cookie(&res).unwrap();
+    assert_ne!(old, new);
+    assert!(store.load(&old).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn expired_session_is_dropped_and_cookie_cleared() {
+    let store = MemoryStore::new();
+    let layer = SessionLayer::new(store.clone()).with_max_age(Duration::from_millis(200));
+    let addr = app(layer).await;
+    let id = session_cookie(&call(addr, "GET", "/count", None).await).unwrap();
+
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let res = call(addr, "GET", "/show", Some(&id)).await;
+
+    assert_eq!(body(&res), " 0");
+    assert_eq!(session_cookie(&res).as_deref(), Some(""));
+    assert!(store.load(&id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn unknown_session_id_clears_cookie() {
+    let addr = app(SessionLayer::new(MemoryStore::new())).await;
+
+    let res = call(addr, "GET", "/show", Some("does-not-exist")).await;
+
+    assert_eq!(session_cookie(&res).as_deref(), Some(""));
+}
+
+#[tokio::test]
+async fn idle_timeout_is_extended_by_each_request() {
+    let store = MemoryStore::new();
+    let layer = SessionLayer::new(store.clone()).with_idle_timeout(Duration::from_millis(500));
+    let addr = app(layer).await;
+    let id = session_cookie(&call(addr, "GET", "/count", None).await).unwrap();
+
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let res = call(addr, "GET", "/show", Some(&id)).await;
+        assert_eq!(body(&res), " 1");
+        assert_eq!(session_cookie(&res), Some(id.clone()));
+    }
+
+    tokio::time::sleep(Duration::from_millis(700)).await;
+    let res = call(addr, "GET", "/show", Some(&id)).await;
+    assert_eq!(body(&res), " 0");
+    assert!(store.load(&id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn session_without_layer_is_a_server_error() {
+    let addr = serve(routes()).await;
+
+    let res = call(addr, "GET", "/show", None).await;
+    assert_eq!(status(&res), 500);
+}
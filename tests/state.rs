@@ -0,0 +1,110 @@
+mod common;
+
+use common::{body, request, send, serve, status};
+use oxidized::{FromRef, Response, Result, Router, State};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+#[derive(Clone)]
+struct AppState {
+    name: &'static str,
+    hits: Counter,
+}
+
+#[derive(Clone)]
+struct Counter(Arc<AtomicUsize>);
+
+impl FromRef<AppState> for Counter {
+    fn from_ref(state: &AppState) -> Self {
+        state.hits.clone()
+    }
+}
+
+async fn whole(State(state): State<AppState>) -> Result<Response> {
+    Ok(Response::new(format!("whole {}", state.name)))
+}
+
+async fn sub(State(Counter(hits)): State<Counter>) -> Result<Response> {
+    let hits = hits.fetch_add(1, Ordering::SeqCst) + 1;
+    Ok(Response::new(format!("sub {}", hits)))
+}
+
+async fn fallback(
+    State(state): State<AppState>,
+    State(Counter(hits)): State<Counter>,
+) -> Result<Response> {
+    Ok(Response::new(format!(
+        "fallback {} {}",
+        state.name,
+        hits.load(Ordering::SeqCst)
+    )))
+}
+
+fn app(state: AppState) -> Router {
+    let nested = Router::new()
+        .get("/whole", whole)
+        .get("/sub", sub)
+        .fallback(fallback);
+    let merged = Router::new()
+        .get("/merged/whole", whole)
+        .get("/merged/sub", sub);
+
+    Router::new()
+        .get("/whole", whole)
+        .nest("/nested", nested)
+        .merge(merged)
+        .fallback(fallback)
+        .with_state(state)
+}
+
+#[tokio::test]
+async fn state_reaches_handlers_through_nest_merge_and_fallback() {
+    let hits = Counter(Arc::new(AtomicUsize::new(0)));
+    let addr = serve(app(AppState {
+        name: "app",
+        hits: hits.clone(),
+    }))
+    .await;
+
+    for (path, expected) in [
+        ("/whole", "whole app"),
+        ("/nested/whole", "whole app"),
+        ("/merged/whole", "whole app"),
+        ("/nested/sub", "sub 1"),
+        ("/merged/sub", "sub 2"),
+        ("/missing", "fallback app 2"),
+        ("/nested/missing", "fallback app 2"),
+    ] {
+        let response = send(addr, &request("GET", path, &[], b"")).await;
+        assert_eq!(status(&response), 200, "{}", path);
+        assert_eq!(body(&response), expected, "{}", path);
+    }
+    assert_eq!(hits.0.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn routers_with_state_applied_can_be_nested_and_merged() {
+    let hits = Counter(Arc::new(AtomicUsize::new(0)));
+    let state = AppState {
+        name: "inner",
+        hits: hits.clone(),
+    };
+    let nested = Router::new()
+        .get("/sub", sub)
+        .fallback(fallback)
+        .with_state(state.clone());
+    let merged = Router::new().get("/whole", whole).with_state(state);
+    let router = Router::new().nest("/nested", nested).merge(merged);
+    let addr = serve(router).await;
+
+    for (path, expected) in [
+        ("/whole", "whole inner"),
+        ("/nested/sub", "sub 1"),
+        ("/nested/missing", "fallback inner 1"),
+    ] {
+        let response = send(addr, &request("GET", path, &[], b"")).await;
+        assert_eq!(body(&response), expected, "{}", path);
+    }
+}
@@ -0,0 +1,101 @@
+use oxidized::{ConnectInfo, ConnectionInfo, Response, Result, Router, Server};
+use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tokio_rustls::{
+    client::TlsStream,
+    rustls::{Certificate, ClientConfig, RootCertStore, ServerName},
+    TlsConnector,
+};
+
+async fn info(ConnectInfo(info): ConnectInfo<ConnectionInfo>) -> Result<Response> {
+    let alpn = info
+        .alpn
+        .map(|alpn| String::from_utf8_lossy(&alpn).into_owned());
+    Ok(Response::new(format!("tls={} alpn={:?}", info.tls, alpn)))
+}
+
+struct TestServer {
+    addr: SocketAddr,
+    root: Certificate,
+}
+
+async fn serve(name: &str) -> TestServer {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let dir = std::env::temp_dir();
+    let cert_path: PathBuf = dir.join(format!("oxidized-{}-{}.crt", name, std::process::id()));
+    let key_path: PathBuf = dir.join(format!("oxidized-{}-{}.key", name, std::process::id()));
+    std::fs::write(&cert_path, cert.serialize_pem().unwrap()).unwrap();
+    std::fs::write(&key_path, cert.serialize_private_key_pem()).unwrap();
+
+    let addr = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let router = Router::new().get("/", info);
+    tokio::spawn(Server::new(router, addr).tls(cert_path, key_path).run());
+    for _ in 0..50 {
+        if TcpStream::connect(addr).await.is_ok() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    TestServer {
+        addr,
+        root: Certificate(cert.serialize_der().unwrap()),
+    }
+}
+
+async fn connect(server: &TestServer, alpn: &[&[u8]]) -> TlsStream<TcpStream> {
+    let mut roots = RootCertStore::empty();
+    roots.add(&server.root).unwrap();
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    config.alpn_protocols = alpn.iter().map(|protocol| protocol.to_vec()).collect();
+
+    let stream = TcpStream::connect(server.addr).await.unwrap();
+    TlsConnector::from(Arc::new(config))
+        .connect(ServerName::try_from("localhost").unwrap(), stream)
+        .await
+        .unwrap()
+}
+
+async fn get(stream: &mut TlsStream<TcpStream>) -> String {
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await
+        .unwrap();
+    let mut response = Vec::new();
+    let _ = tokio::time::timeout(Duration::from_secs(2), stream.read_to_end(&mut response)).await;
+    String::from_utf8_lossy(&response).into_owned()
+}
+
+#[tokio::test]
+async fn negotiates_http1_over_alpn() {
+    let server = serve("alpn").await;
+    let mut stream = connect(&server, &[b"h2", b"http/1.1"]).await;
+
+    assert_eq!(stream.get_ref().1.alpn_protocol(), Some(&b"http/1.1"[..]));
+    let response = get(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+    assert!(
+        response.ends_with("tls=true alpn=Some(\"http/1.1\")"),
+        "{}",
+        response
+    );
+}
+
+#[tokio::test]
+async fn alpn_is_none_without_client_protocols() {
+    let server = serve("no-alpn").await;
+    let mut stream = connect(&server, &[]).await;
+
+    assert_eq!(stream.get_ref().1.alpn_protocol(), None);
+    let response = get(&mut stream).await;
+    assert!(response.ends_with("tls=true alpn=None"), "{}", response);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{body, header, request, send, serve, status};
+use oxidized::{
+    headers::{Accept, ContentType, UserAgent},
+    Response, Result, Router, TypedHeader,
+};
+
+async fn negotiate(
+    TypedHeader(accept): TypedHeader<Accept>,
+    TypedHeader(agent): TypedHeader<UserAgent>,
+) -> Result<Response> {
+    let candidates = [
+        "application/json".parse().unwrap(),
+        "text/html".parse().unwrap(),
+    ];
+    let preferred = accept.preferred(&candidates).cloned();
+    Ok(Response::new(format!(
+        "{} {:?}",
+        agent,
+        preferred.map(|mime| mime.to_string())
+    ))
+    .typed_header(ContentType::text_utf8()))
+}
+
+async fn get(headers: &[(&str, &str)]) -> String {
+    let addr = serve(Router::new().get("/", negotiate)).await;
+    send(addr, &request("GET", "/", headers, b"")).await
+}
+
+#[tokio::test]
+async fn decodes_typed_headers() {
+    let response = get(&[
+        ("Accept", "text/html;q=0.5, application/json;q=0.9"),
+        ("User-Agent", "curl/8.0"),
+    ])
+    .await;
+    assert_eq!(status(&response), 200);
+    assert_eq!(body(&response), r#"curl/8.0 Some("application/json")"#);
+    assert_eq!(
+        header(&response, "content-type"),
+        Some("text/plain; charset=utf-8")
+    );
+}
+
+#[tokio::test]
+async fn missing_header_is_400() {
+    let response = get(&[("User-Agent", "curl/8.0")]).await;
+    assert_eq!(status(&response), 400);
+    assert_eq!(body(&response), "Missing request header `accept`");
+}
+
+#[tokio::test]
+async fn invalid_header_is_400() {
+    let response = get(&[("Accept", "text/html;q=2"), ("User-Agent", "curl/8.0")]).await;
+    assert_eq!(status(&response), 400);
+    assert!(
+        body(&response).starts_with("Invalid request header `accept`"),
+        "{}",
+        body(&response)
+    );
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{body, header, request, send, serve, status};
+use oxidized::{
+    extractor::validated::Regex, Json, Response, Result, Router, Validate, Validated,
+    ValidationErrors,
+};
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+#[derive(Deserialize)]
+struct CreateUser {
+    username: String,
+    age: u32,
+}
+
+impl Validate for CreateUser {
+    fn validate(&self) -> std::result::Result<(), ValidationErrors> {
+        static USERNAME: OnceLock<Regex> = OnceLock::new();
+        let username = USERNAME.get_or_init(|| Regex::new("^[a-z0-9_]+$").unwrap());
+        let mut errors = ValidationErrors::new();
+        errors
+            .field("username", &self.username)
+            .length(3..=32)
+            .matches(username, "must be lowercase letters, digits or underscores");
+        errors.field("age", &self.age).range(18..);
+        errors.into_result()
+    }
+}
+
+async fn create_user(Validated(Json(user)): Validated<Json<CreateUser>>) -> Result<Response> {
+    Ok(Response::new(format!("created {}", user.username)))
+}
+
+async fn post(content_type: &str, body: &str) -> String {
+    let addr = serve(Router::new().post("/users", create_user)).await;
+    let headers = [("content-type", content_type)];
+    send(addr, &request("POST", "/users", &headers, body.as_bytes())).await
+}
+
+#[tokio::test]
+async fn valid_value_reaches_the_handler() {
+    let response = post("application/json", r#"{"username":"alice","age":30}"#).await;
+    assert_eq!(status(&response), 200);
+    assert_eq!(body(&response), "created alice");
+}
+
+#[tokio::test]
+async fn invalid_value_lists_every_violation() {
+    let response = post("application/json", r#"{"username":"Al","age":17}"#).await;
+    assert_eq!(status(&response), 422);
+    assert_eq!(header(&response, "content-type"), Some("application/json"));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(body(&response)).unwrap(),
+        serde_json::json!({
+            "errors": [
+                {"field": "username", "message": "length must be between 3 and 32"},
+                {"field": "username", "message": "must be lowercase letters, digits or underscores"},
+                {"field": "age", "message": "must be at least 18"},
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+async fn inner_rejection_passes_through() {
+    let response = post("text/plain", r#"{"username":"alice","age":30}"#).await;
+    assert_eq!(status(&response), 415);
+    assert_eq!(
+        body(&response),
+        "Expected request with `Content-Type: application/json`"
+    );
+
+    let response = post("application/json", r#"{"username":"alice""#).await;
+    assert_eq!(status(&response), 400);
+}